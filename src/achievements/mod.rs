@@ -0,0 +1,178 @@
+pub mod resources;
+mod systems;
+
+use crate::game::events::DamageSource;
+use crate::game::run_stats::resources::RunStats;
+use crate::game::{GameState, MAX_DEPTH};
+use crate::settings::difficulty::Difficulty;
+use crate::settings::GameMode;
+use resources::*;
+use systems::*;
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Unlocked achievements and high scores are stored next to the executable
+const ACHIEVEMENTS_FILE_PATH: &str = "achievements.ron";
+
+pub const ACHIEVEMENTS: [Achievement; 5] = [
+    Achievement {
+        id: "untouchable",
+        name: "Untouchable",
+        description: "Reach depth 100 without taking damage",
+        condition: AchievementCondition::DepthWithoutDamage(100.0),
+    },
+    Achievement {
+        id: "return_to_sender",
+        name: "Return to Sender",
+        description: "Kill the boss with a reflected projectile",
+        condition: AchievementCondition::BossKilledBy(DamageSource::ReflectedProjectile),
+    },
+    Achievement {
+        id: "pyromaniac",
+        name: "Pyromaniac",
+        description: "Trigger 5 fire waves in one run",
+        condition: AchievementCondition::FireWaves(5),
+    },
+    Achievement {
+        id: "combo_artist",
+        name: "Combo Artist",
+        description: "Reach a 25 combo",
+        condition: AchievementCondition::ComboReached(25),
+    },
+    Achievement {
+        id: "rock_bottom",
+        name: "Rock Bottom",
+        description: "Reach the bottom of the pit",
+        condition: AchievementCondition::DepthReached(MAX_DEPTH),
+    },
+];
+
+pub struct AchievementsPlugin;
+
+impl Plugin for AchievementsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<UnlockedAchievements>()
+            .init_resource::<HighScores>()
+            .init_resource::<AchievementsStorage>()
+            .add_event::<AchievementUnlockedEvent>()
+            .add_startup_system(load_achievements)
+            .add_system(check_achievements.in_set(OnUpdate(GameState::Running)))
+            // Run ends either with the game over or with the boss kill
+            .add_system(record_high_score.in_schedule(OnEnter(GameState::Gameover)))
+            .add_system(
+                record_high_score
+                    .run_if(is_boss_killed)
+                    .in_set(OnUpdate(GameState::Running)),
+            )
+            .add_system(
+                save_achievements.run_if(can_save_achievements).run_if(
+                    resource_changed::<UnlockedAchievements>()
+                        .or_else(resource_changed::<HighScores>()),
+                ),
+            );
+    }
+}
+
+pub struct Achievement {
+    // Stored in the achievements file, so it must never change
+    pub id: &'static str,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub condition: AchievementCondition,
+}
+
+// What has to happen during a single run
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum AchievementCondition {
+    DepthReached(f32),
+    DepthWithoutDamage(f32),
+    BossKilledBy(DamageSource),
+    FireWaves(usize),
+    ComboReached(usize),
+}
+
+impl AchievementCondition {
+    pub fn is_met(&self, run_stats: &RunStats) -> bool {
+        match *self {
+            AchievementCondition::DepthReached(depth) => run_stats.depth >= depth,
+            AchievementCondition::DepthWithoutDamage(depth) => {
+                run_stats.depth >= depth && run_stats.total_damage_taken() == 0
+            }
+            AchievementCondition::BossKilledBy(source) => run_stats.boss_killed_by == Some(source),
+            AchievementCondition::FireWaves(fire_waves) => run_stats.fire_waves >= fire_waves,
+            AchievementCondition::ComboReached(combo) => run_stats.highest_combo >= combo,
+        }
+    }
+}
+
+// Contents of the achievements file. Both fields are required,
+// otherwise the old files would be read as empty ones.
+#[derive(Serialize, Deserialize)]
+struct AchievementsFile {
+    unlocked_achievements: UnlockedAchievements,
+    high_scores: HighScores,
+}
+
+// Achievements file from before the game modes, everything goes to the standard mode
+#[derive(Deserialize)]
+struct DifficultyAchievementsFile {
+    unlocked_achievements: ByDifficulty<Vec<String>>,
+    high_scores: ByDifficulty<usize>,
+}
+
+#[derive(Deserialize)]
+struct ByDifficulty<T> {
+    by_difficulty: BTreeMap<Difficulty, T>,
+}
+
+impl From<DifficultyAchievementsFile> for AchievementsFile {
+    fn from(difficulty_file: DifficultyAchievementsFile) -> Self {
+        let by_mode = |difficulty| (GameMode::Standard, difficulty);
+
+        AchievementsFile {
+            unlocked_achievements: UnlockedAchievements {
+                by_mode: difficulty_file
+                    .unlocked_achievements
+                    .by_difficulty
+                    .into_iter()
+                    .map(|(difficulty, ids)| (by_mode(difficulty), ids))
+                    .collect(),
+            },
+            high_scores: HighScores {
+                by_mode: difficulty_file
+                    .high_scores
+                    .by_difficulty
+                    .into_iter()
+                    .map(|(difficulty, score)| (by_mode(difficulty), score))
+                    .collect(),
+            },
+        }
+    }
+}
+
+// Achievements file from before the difficulties, the unlocks go to the default difficulty
+#[derive(Deserialize)]
+struct LegacyAchievementsFile {
+    ids: Vec<String>,
+}
+
+impl From<LegacyAchievementsFile> for AchievementsFile {
+    fn from(legacy_file: LegacyAchievementsFile) -> Self {
+        let mut unlocked_achievements = UnlockedAchievements::default();
+        for id in legacy_file.ids.iter() {
+            unlocked_achievements.unlock(GameMode::Standard, Difficulty::default(), id);
+        }
+
+        AchievementsFile {
+            unlocked_achievements,
+            high_scores: HighScores::default(),
+        }
+    }
+}
+
+pub struct AchievementUnlockedEvent {
+    pub achievement: &'static Achievement,
+}
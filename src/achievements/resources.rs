@@ -0,0 +1,50 @@
+use crate::settings::difficulty::Difficulty;
+use crate::settings::GameMode;
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Achievements unlocked in all the runs, separately for every game mode and difficulty
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct UnlockedAchievements {
+    pub by_mode: BTreeMap<(GameMode, Difficulty), Vec<String>>,
+}
+
+impl UnlockedAchievements {
+    pub fn is_unlocked(&self, game_mode: GameMode, difficulty: Difficulty, id: &str) -> bool {
+        self.by_mode
+            .get(&(game_mode, difficulty))
+            .is_some_and(|ids| ids.iter().any(|unlocked_id| unlocked_id == id))
+    }
+
+    pub fn unlock(&mut self, game_mode: GameMode, difficulty: Difficulty, id: &str) {
+        self.by_mode
+            .entry((game_mode, difficulty))
+            .or_default()
+            .push(id.to_string());
+    }
+}
+
+// Best score reached in every game mode and difficulty, endless scores aren't comparable
+#[derive(Resource, Debug, Default, Clone, Serialize, Deserialize)]
+pub struct HighScores {
+    pub by_mode: BTreeMap<(GameMode, Difficulty), usize>,
+}
+
+impl HighScores {
+    pub fn best(&self, game_mode: GameMode, difficulty: Difficulty) -> usize {
+        self.by_mode
+            .get(&(game_mode, difficulty))
+            .copied()
+            .unwrap_or(0)
+    }
+}
+
+// Achievements file is only written, after it was loaded or found missing,
+// so an unreadable file isn't overwritten with the empty defaults
+#[derive(Resource, Debug, Default)]
+pub struct AchievementsStorage {
+    pub can_save: bool,
+}
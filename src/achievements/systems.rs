@@ -0,0 +1,105 @@
+use super::resources::{AchievementsStorage, HighScores, UnlockedAchievements};
+use super::{AchievementUnlockedEvent, AchievementsFile};
+use super::{DifficultyAchievementsFile, LegacyAchievementsFile};
+use super::{ACHIEVEMENTS, ACHIEVEMENTS_FILE_PATH};
+use crate::game::run_stats::resources::RunStats;
+
+use bevy::prelude::*;
+
+pub fn load_achievements(
+    mut unlocked_achievements: ResMut<UnlockedAchievements>,
+    mut high_scores: ResMut<HighScores>,
+    mut achievements_storage: ResMut<AchievementsStorage>,
+) {
+    let contents = match std::fs::read_to_string(ACHIEVEMENTS_FILE_PATH) {
+        Ok(contents) => contents,
+        // First launch, nothing to lose
+        Err(error) if error.kind() == std::io::ErrorKind::NotFound => {
+            achievements_storage.can_save = true;
+            return;
+        }
+        Err(error) => {
+            println!("Failed to read {}: {}", ACHIEVEMENTS_FILE_PATH, error);
+            return;
+        }
+    };
+
+    // Older formats are converted, the error of the current one is reported
+    let achievements_file = ron::from_str::<AchievementsFile>(&contents).or_else(|error| {
+        ron::from_str::<DifficultyAchievementsFile>(&contents)
+            .map(AchievementsFile::from)
+            .or_else(|_| {
+                ron::from_str::<LegacyAchievementsFile>(&contents).map(AchievementsFile::from)
+            })
+            .map_err(|_| error)
+    });
+
+    match achievements_file {
+        Ok(achievements_file) => {
+            *unlocked_achievements = achievements_file.unlocked_achievements;
+            *high_scores = achievements_file.high_scores;
+            achievements_storage.can_save = true;
+        }
+        Err(error) => println!("Failed to parse {}: {}", ACHIEVEMENTS_FILE_PATH, error),
+    }
+}
+
+pub fn can_save_achievements(achievements_storage: Res<AchievementsStorage>) -> bool {
+    achievements_storage.can_save
+}
+
+pub fn save_achievements(
+    unlocked_achievements: Res<UnlockedAchievements>,
+    high_scores: Res<HighScores>,
+) {
+    let achievements_file = AchievementsFile {
+        unlocked_achievements: unlocked_achievements.clone(),
+        high_scores: high_scores.clone(),
+    };
+
+    let contents =
+        match ron::ser::to_string_pretty(&achievements_file, ron::ser::PrettyConfig::default()) {
+            Ok(contents) => contents,
+            Err(error) => {
+                println!("Failed to serialize the achievements: {}", error);
+                return;
+            }
+        };
+
+    if let Err(error) = std::fs::write(ACHIEVEMENTS_FILE_PATH, contents) {
+        println!("Failed to save {}: {}", ACHIEVEMENTS_FILE_PATH, error);
+    }
+}
+
+pub fn check_achievements(
+    mut achievement_unlocked_event_writer: EventWriter<AchievementUnlockedEvent>,
+    mut unlocked_achievements: ResMut<UnlockedAchievements>,
+    run_stats: Res<RunStats>,
+) {
+    for achievement in ACHIEVEMENTS.iter() {
+        // Only the new unlocks touch the resource, so it isn't saved every frame
+        if unlocked_achievements.is_unlocked(
+            run_stats.game_mode,
+            run_stats.difficulty,
+            achievement.id,
+        ) || !achievement.condition.is_met(&run_stats)
+        {
+            continue;
+        }
+
+        unlocked_achievements.unlock(run_stats.game_mode, run_stats.difficulty, achievement.id);
+        achievement_unlocked_event_writer.send(AchievementUnlockedEvent { achievement });
+    }
+}
+
+pub fn is_boss_killed(run_stats: Res<RunStats>) -> bool {
+    run_stats.is_boss_killed
+}
+
+pub fn record_high_score(mut high_scores: ResMut<HighScores>, run_stats: Res<RunStats>) {
+    // Same as with the achievements, the resource is only touched by a new best
+    let key = (run_stats.game_mode, run_stats.difficulty);
+    if run_stats.score > high_scores.best(key.0, key.1) {
+        high_scores.by_mode.insert(key, run_stats.score);
+    }
+}
@@ -0,0 +1,9 @@
+use super::InputAction;
+
+use bevy::prelude::*;
+
+// On-screen button, which triggers the action while touched
+#[derive(Component)]
+pub struct TouchActionButton {
+    pub action: InputAction,
+}
@@ -0,0 +1,158 @@
+pub mod components;
+pub mod resources;
+mod systems;
+
+use crate::game::GameState;
+use resources::*;
+use systems::*;
+
+use bevy::input::InputSystem;
+use bevy::prelude::*;
+use bevy::ui::UiSystem;
+use serde::{Deserialize, Serialize};
+
+// Left stick values below this don't count as the player picking up the gamepad
+const GAMEPAD_ACTIVITY_THRESHOLD: f32 = 0.5;
+
+// Two taps closer than this in time (seconds) and space (pixels) fire the chainsaw
+const DOUBLE_TAP_WINDOW: f64 = 0.3;
+const DOUBLE_TAP_DISTANCE: f32 = 48.0;
+
+pub struct ControlsPlugin;
+
+impl Plugin for ControlsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActiveInputDevice>()
+            .init_resource::<InputBindings>()
+            .init_resource::<ActionState>()
+            .init_resource::<RebindState>()
+            .init_resource::<TouchControls>()
+            // Translate device state into actions before the game reads them.
+            // Runs after the UI, so touches on the on-screen buttons don't steer the player.
+            .add_systems(
+                (
+                    handle_gamepad_connections,
+                    detect_active_input_device,
+                    track_touches,
+                    capture_rebinding.run_if(is_rebinding),
+                    update_action_state,
+                )
+                    .chain()
+                    .in_base_set(CoreSet::PreUpdate)
+                    .after(InputSystem)
+                    .after(UiSystem::Focus),
+            )
+            .add_system(
+                pause_on_gamepad_disconnect
+                    .after(handle_gamepad_connections)
+                    .in_base_set(CoreSet::PreUpdate)
+                    .run_if(in_state(GameState::Running)),
+            );
+    }
+}
+
+// Device the player has used last. Prompts and menus adapt to it.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum InputDevice {
+    #[default]
+    KeyboardMouse,
+    Gamepad,
+    Touch,
+}
+
+// Everything the player can do. Gameplay reads these instead of the devices.
+// Move is made of the four directions, the left stick or the cursor (see MoveInput).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum InputAction {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    ActivateChainsaw,
+    Pause,
+    Confirm,
+}
+
+impl InputAction {
+    pub const ALL: [InputAction; 7] = [
+        InputAction::MoveUp,
+        InputAction::MoveDown,
+        InputAction::MoveLeft,
+        InputAction::MoveRight,
+        InputAction::ActivateChainsaw,
+        InputAction::Pause,
+        InputAction::Confirm,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            InputAction::MoveUp => "Up",
+            InputAction::MoveDown => "Down",
+            InputAction::MoveLeft => "Left",
+            InputAction::MoveRight => "Right",
+            InputAction::ActivateChainsaw => "Chainsaw",
+            InputAction::Pause => "Pause",
+            InputAction::Confirm => "Confirm",
+        }
+    }
+}
+
+// Keyboard or mouse button, which triggers an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum InputBinding {
+    Key(KeyCode),
+    Mouse(MouseButton),
+}
+
+impl InputBinding {
+    pub fn name(&self) -> String {
+        match self {
+            InputBinding::Key(key) => format!("{:?}", key),
+            InputBinding::Mouse(MouseButton::Left) => "LMB".to_string(),
+            InputBinding::Mouse(MouseButton::Right) => "RMB".to_string(),
+            InputBinding::Mouse(MouseButton::Middle) => "MMB".to_string(),
+            InputBinding::Mouse(MouseButton::Other(index)) => format!("Mouse {}", index),
+        }
+    }
+}
+
+pub fn gamepad_button_name(button_type: GamepadButtonType) -> String {
+    match button_type {
+        GamepadButtonType::South => "A".to_string(),
+        GamepadButtonType::East => "B".to_string(),
+        GamepadButtonType::North => "Y".to_string(),
+        GamepadButtonType::West => "X".to_string(),
+        GamepadButtonType::LeftTrigger => "LB".to_string(),
+        GamepadButtonType::RightTrigger => "RB".to_string(),
+        GamepadButtonType::LeftTrigger2 => "LT".to_string(),
+        GamepadButtonType::RightTrigger2 => "RT".to_string(),
+        other => format!("{:?}", other),
+    }
+}
+
+// Where the player wants to move this frame
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MoveInput {
+    // Steer toward the world position under the cursor
+    Cursor(Vec2),
+    // Pressed directions, each axis is -1, 0 or 1
+    Digital(Vec2),
+    // Stick position, with length up to 1
+    Analog(Vec2),
+}
+
+impl Default for MoveInput {
+    fn default() -> Self {
+        MoveInput::Digital(Vec2::ZERO)
+    }
+}
+
+// Run condition, which is true while the player uses a connected gamepad
+pub fn gamepad_is_active(active_input_device: Res<ActiveInputDevice>) -> bool {
+    active_input_device.device == InputDevice::Gamepad && active_input_device.gamepad.is_some()
+}
+
+// Run condition, which is true while the settings wait for a new binding
+pub fn is_rebinding(rebind_state: Res<RebindState>) -> bool {
+    rebind_state.action.is_some()
+}
@@ -0,0 +1,163 @@
+use std::collections::{HashMap, HashSet};
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{gamepad_button_name, InputAction, InputBinding, InputDevice, MoveInput};
+
+#[derive(Resource, Default, Debug)]
+pub struct ActiveInputDevice {
+    pub device: InputDevice,
+    // Gamepad, which controls the player. None when no gamepad is connected.
+    pub gamepad: Option<Gamepad>,
+    // Set for one frame, when the controlling gamepad gets unplugged
+    pub gamepad_lost: bool,
+}
+
+impl ActiveInputDevice {
+    // Returns the button of the controlling gamepad
+    pub fn gamepad_button(&self, button_type: GamepadButtonType) -> Option<GamepadButton> {
+        self.gamepad
+            .map(|gamepad| GamepadButton::new(gamepad, button_type))
+    }
+
+    // Returns the axis of the controlling gamepad
+    pub fn gamepad_axis(&self, axis_type: GamepadAxisType) -> Option<GamepadAxis> {
+        self.gamepad
+            .map(|gamepad| GamepadAxis::new(gamepad, axis_type))
+    }
+}
+
+// Buttons of every device, which trigger one action
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ActionBindings {
+    pub keyboard_mouse: Vec<InputBinding>,
+    pub gamepad: Vec<GamepadButtonType>,
+}
+
+impl ActionBindings {
+    fn new(keyboard_mouse: &[InputBinding], gamepad: &[GamepadButtonType]) -> Self {
+        ActionBindings {
+            keyboard_mouse: keyboard_mouse.to_vec(),
+            gamepad: gamepad.to_vec(),
+        }
+    }
+}
+
+// Maps every action to the buttons, which trigger it. Saved to the config file.
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+pub struct InputBindings {
+    pub bindings: HashMap<InputAction, ActionBindings>,
+}
+
+impl Default for InputBindings {
+    fn default() -> Self {
+        use GamepadButtonType::*;
+        use InputBinding::*;
+
+        let bindings = HashMap::from([
+            (
+                InputAction::MoveUp,
+                ActionBindings::new(&[Key(KeyCode::W), Key(KeyCode::Up)], &[DPadUp]),
+            ),
+            (
+                InputAction::MoveDown,
+                ActionBindings::new(&[Key(KeyCode::S), Key(KeyCode::Down)], &[DPadDown]),
+            ),
+            (
+                InputAction::MoveLeft,
+                ActionBindings::new(&[Key(KeyCode::A), Key(KeyCode::Left)], &[DPadLeft]),
+            ),
+            (
+                InputAction::MoveRight,
+                ActionBindings::new(&[Key(KeyCode::D), Key(KeyCode::Right)], &[DPadRight]),
+            ),
+            (
+                InputAction::ActivateChainsaw,
+                ActionBindings::new(
+                    &[Mouse(MouseButton::Left), Key(KeyCode::Space)],
+                    &[RightTrigger2],
+                ),
+            ),
+            (
+                InputAction::Pause,
+                ActionBindings::new(&[Key(KeyCode::Escape)], &[Start]),
+            ),
+            (
+                InputAction::Confirm,
+                ActionBindings::new(&[Key(KeyCode::Return)], &[South]),
+            ),
+        ]);
+
+        InputBindings { bindings }
+    }
+}
+
+impl InputBindings {
+    pub fn get(&self, action: InputAction) -> Option<&ActionBindings> {
+        self.bindings.get(&action)
+    }
+
+    // Name of the first button of the action for the given device, used in prompts
+    pub fn prompt(&self, action: InputAction, device: InputDevice) -> String {
+        let Some(action_bindings) = self.get(action) else {
+            return "-".to_string();
+        };
+
+        let prompt = match device {
+            InputDevice::KeyboardMouse => action_bindings
+                .keyboard_mouse
+                .first()
+                .map(|binding| binding.name()),
+            InputDevice::Gamepad => action_bindings
+                .gamepad
+                .first()
+                .map(|button_type| gamepad_button_name(*button_type)),
+            InputDevice::Touch => Some(
+                if action == InputAction::ActivateChainsaw {
+                    "Double tap"
+                } else {
+                    "Tap"
+                }
+                .to_string(),
+            ),
+        };
+
+        prompt.unwrap_or_else(|| "-".to_string())
+    }
+}
+
+// Actions triggered this frame
+#[derive(Resource, Default, Debug)]
+pub struct ActionState {
+    pub movement: MoveInput,
+    pub pressed: HashSet<InputAction>,
+    pub just_pressed: HashSet<InputAction>,
+}
+
+impl ActionState {
+    pub fn pressed(&self, action: InputAction) -> bool {
+        self.pressed.contains(&action)
+    }
+
+    pub fn just_pressed(&self, action: InputAction) -> bool {
+        self.just_pressed.contains(&action)
+    }
+}
+
+// Action, which waits for the player to press its new button in the settings
+#[derive(Resource, Default, Debug)]
+pub struct RebindState {
+    pub action: Option<InputAction>,
+    // The click that started the rebinding has to be released first
+    pub armed: bool,
+}
+
+#[derive(Resource, Default, Debug)]
+pub struct TouchControls {
+    // Finger, which the player follows. Touches of the on-screen buttons don't steer.
+    pub steering_touch: Option<u64>,
+    // Time and window position of the last tap
+    pub last_tap: Option<(f64, Vec2)>,
+    pub double_tapped: bool,
+}
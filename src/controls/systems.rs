@@ -0,0 +1,344 @@
+use super::components::TouchActionButton;
+use super::resources::{ActionState, ActiveInputDevice, InputBindings, RebindState, TouchControls};
+use super::{
+    InputAction, InputBinding, InputDevice, MoveInput, DOUBLE_TAP_DISTANCE, DOUBLE_TAP_WINDOW,
+    GAMEPAD_ACTIVITY_THRESHOLD,
+};
+use crate::game::{GameInfo, GameState};
+use crate::settings::resources::GameSettings;
+use crate::settings::ControlScheme;
+
+use bevy::input::gamepad::{GamepadConnection, GamepadConnectionEvent};
+use bevy::input::mouse::MouseMotion;
+use bevy::prelude::*;
+
+// Picks up newly plugged gamepads and lets go of the unplugged ones.
+// When the controlling gamepad is lost, fall back to another one or to keyboard and mouse.
+pub fn handle_gamepad_connections(
+    mut gamepad_connection_event_reader: EventReader<GamepadConnectionEvent>,
+    mut active_input_device: ResMut<ActiveInputDevice>,
+    gamepads: Res<Gamepads>,
+) {
+    active_input_device.gamepad_lost = false;
+
+    for connection_event in gamepad_connection_event_reader.iter() {
+        match &connection_event.connection {
+            GamepadConnection::Connected(_) => {
+                if active_input_device.gamepad.is_none() {
+                    active_input_device.gamepad = Some(connection_event.gamepad);
+                    active_input_device.device = InputDevice::Gamepad;
+                }
+            }
+            GamepadConnection::Disconnected => {
+                if active_input_device.gamepad != Some(connection_event.gamepad) {
+                    continue;
+                }
+
+                active_input_device.gamepad = gamepads
+                    .iter()
+                    .find(|gamepad| *gamepad != connection_event.gamepad);
+
+                if active_input_device.device == InputDevice::Gamepad {
+                    active_input_device.gamepad_lost = true;
+
+                    if active_input_device.gamepad.is_none() {
+                        active_input_device.device = InputDevice::KeyboardMouse;
+                    }
+                }
+            }
+        }
+    }
+}
+
+// Switches the active device to the one the player has touched last
+#[allow(clippy::too_many_arguments)]
+pub fn detect_active_input_device(
+    mut active_input_device: ResMut<ActiveInputDevice>,
+    mut mouse_motion_event_reader: EventReader<MouseMotion>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    gamepads: Res<Gamepads>,
+    touches: Res<Touches>,
+) {
+    let mouse_moved = mouse_motion_event_reader.iter().count() > 0;
+
+    if touches.any_just_pressed() {
+        active_input_device.device = InputDevice::Touch;
+        return;
+    }
+
+    // Browsers emulate the mouse while the screen is touched
+    if touches.iter().next().is_some() {
+        return;
+    }
+
+    if keyboard_input.get_just_pressed().next().is_some()
+        || mouse_input.get_just_pressed().next().is_some()
+        || mouse_moved
+    {
+        active_input_device.device = InputDevice::KeyboardMouse;
+        return;
+    }
+
+    for gamepad in gamepads.iter() {
+        let button_pressed = gamepad_input
+            .get_just_pressed()
+            .any(|button| button.gamepad == gamepad);
+        let stick_moved = [GamepadAxisType::LeftStickX, GamepadAxisType::LeftStickY]
+            .into_iter()
+            .filter_map(|axis_type| gamepad_axes.get(GamepadAxis::new(gamepad, axis_type)))
+            .any(|value| value.abs() > GAMEPAD_ACTIVITY_THRESHOLD);
+
+        if button_pressed || stick_moved {
+            active_input_device.device = InputDevice::Gamepad;
+            active_input_device.gamepad = Some(gamepad);
+            return;
+        }
+    }
+}
+
+// Picks the finger, which steers the player, and detects double taps
+pub fn track_touches(
+    mut touch_controls: ResMut<TouchControls>,
+    button_query: Query<&Interaction, With<Button>>,
+    touches: Res<Touches>,
+    time: Res<Time>,
+) {
+    touch_controls.double_tapped = false;
+
+    if let Some(steering_touch) = touch_controls.steering_touch {
+        if touches.get_pressed(steering_touch).is_none() {
+            touch_controls.steering_touch = None;
+        }
+    }
+
+    let is_button_touched = button_query
+        .iter()
+        .any(|interaction| *interaction == Interaction::Clicked);
+
+    for touch in touches.iter_just_pressed() {
+        if is_button_touched {
+            continue;
+        }
+
+        let now = time.elapsed_seconds_f64();
+
+        if let Some((last_tap_time, last_tap_position)) = touch_controls.last_tap {
+            if now - last_tap_time <= DOUBLE_TAP_WINDOW
+                && last_tap_position.distance(touch.position()) <= DOUBLE_TAP_DISTANCE
+            {
+                touch_controls.double_tapped = true;
+            }
+        }
+
+        touch_controls.last_tap = Some((now, touch.position()));
+
+        if touch_controls.steering_touch.is_none() {
+            touch_controls.steering_touch = Some(touch.id());
+        }
+    }
+}
+
+// Don't let the player die while looking for the cable
+pub fn pause_on_gamepad_disconnect(
+    active_input_device: Res<ActiveInputDevice>,
+    mut next_game_state: ResMut<NextState<GameState>>,
+) {
+    if active_input_device.gamepad_lost {
+        next_game_state.set(GameState::Paused);
+    }
+}
+
+// Assigns the next pressed button to the action picked in the settings.
+// Escape cancels the rebinding.
+pub fn capture_rebinding(
+    mut rebind_state: ResMut<RebindState>,
+    mut input_bindings: ResMut<InputBindings>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+) {
+    let Some(action) = rebind_state.action else {
+        return;
+    };
+
+    if !rebind_state.armed {
+        rebind_state.armed = mouse_input.get_pressed().next().is_none();
+        return;
+    }
+
+    // Escape cancels, unless it's the button being bound to the pause
+    if action != InputAction::Pause && keyboard_input.just_pressed(KeyCode::Escape) {
+        rebind_state.action = None;
+        return;
+    }
+
+    let new_binding = keyboard_input
+        .get_just_pressed()
+        .next()
+        .map(|key| InputBinding::Key(*key))
+        .or_else(|| {
+            mouse_input
+                .get_just_pressed()
+                .next()
+                .map(|button| InputBinding::Mouse(*button))
+        });
+    let new_gamepad_binding = gamepad_input
+        .get_just_pressed()
+        .next()
+        .map(|button| button.button_type);
+
+    if new_binding.is_none() && new_gamepad_binding.is_none() {
+        return;
+    }
+
+    let action_bindings = input_bindings.bindings.entry(action).or_default();
+
+    if let Some(new_binding) = new_binding {
+        replace_primary_binding(&mut action_bindings.keyboard_mouse, new_binding);
+    }
+    if let Some(new_gamepad_binding) = new_gamepad_binding {
+        replace_primary_binding(&mut action_bindings.gamepad, new_gamepad_binding);
+    }
+
+    rebind_state.action = None;
+}
+
+// Rebinding edits the first binding, which is shown in the prompts. The others stay.
+fn replace_primary_binding<T: PartialEq>(bindings: &mut Vec<T>, new_binding: T) {
+    // Already bound button just becomes the first one
+    if let Some(index) = bindings.iter().position(|binding| *binding == new_binding) {
+        bindings.swap(0, index);
+    } else if bindings.is_empty() {
+        bindings.push(new_binding);
+    } else {
+        bindings[0] = new_binding;
+    }
+}
+
+// Translates the device state into actions using the bindings
+#[allow(clippy::too_many_arguments)]
+pub fn update_action_state(
+    mut action_state: ResMut<ActionState>,
+    input_bindings: Res<InputBindings>,
+    active_input_device: Res<ActiveInputDevice>,
+    rebind_state: Res<RebindState>,
+    settings: Res<GameSettings>,
+    game_info: Res<GameInfo>,
+    keyboard_input: Res<Input<KeyCode>>,
+    mouse_input: Res<Input<MouseButton>>,
+    gamepad_input: Res<Input<GamepadButton>>,
+    gamepad_axes: Res<Axis<GamepadAxis>>,
+    touch_controls: Res<TouchControls>,
+    touch_button_query: Query<(Ref<Interaction>, &TouchActionButton)>,
+) {
+    action_state.pressed.clear();
+    action_state.just_pressed.clear();
+
+    // Buttons pressed while rebinding shouldn't do anything else
+    if rebind_state.action.is_some() {
+        action_state.movement = MoveInput::default();
+        return;
+    }
+
+    for action in InputAction::ALL {
+        let Some(action_bindings) = input_bindings.get(action) else {
+            continue;
+        };
+
+        let mut pressed = false;
+        let mut just_pressed = false;
+
+        for binding in action_bindings.keyboard_mouse.iter() {
+            match binding {
+                InputBinding::Key(key) => {
+                    pressed |= keyboard_input.pressed(*key);
+                    just_pressed |= keyboard_input.just_pressed(*key);
+                }
+                InputBinding::Mouse(button) => {
+                    pressed |= mouse_input.pressed(*button);
+                    just_pressed |= mouse_input.just_pressed(*button);
+                }
+            }
+        }
+
+        for button_type in action_bindings.gamepad.iter() {
+            if let Some(button) = active_input_device.gamepad_button(*button_type) {
+                pressed |= gamepad_input.pressed(button);
+                just_pressed |= gamepad_input.just_pressed(button);
+            }
+        }
+
+        if pressed {
+            action_state.pressed.insert(action);
+        }
+        if just_pressed {
+            action_state.just_pressed.insert(action);
+        }
+    }
+
+    // On-screen buttons and double taps
+    for (interaction, touch_action_button) in touch_button_query.iter() {
+        if *interaction == Interaction::Clicked {
+            action_state.pressed.insert(touch_action_button.action);
+
+            if interaction.is_changed() {
+                action_state.just_pressed.insert(touch_action_button.action);
+            }
+        }
+    }
+
+    if touch_controls.double_tapped {
+        action_state.pressed.insert(InputAction::ActivateChainsaw);
+        action_state
+            .just_pressed
+            .insert(InputAction::ActivateChainsaw);
+    }
+
+    let mut digital_movement = Vec2::ZERO;
+
+    if action_state.pressed(InputAction::MoveUp) {
+        digital_movement.y += 1.0;
+    }
+    if action_state.pressed(InputAction::MoveDown) {
+        digital_movement.y -= 1.0;
+    }
+    if action_state.pressed(InputAction::MoveLeft) {
+        digital_movement.x -= 1.0;
+    }
+    if action_state.pressed(InputAction::MoveRight) {
+        digital_movement.x += 1.0;
+    }
+
+    action_state.movement = match active_input_device.device {
+        InputDevice::Gamepad => {
+            let stick_value = |axis_type| {
+                active_input_device
+                    .gamepad_axis(axis_type)
+                    .and_then(|axis| gamepad_axes.get(axis))
+                    .unwrap_or(0.0)
+            };
+            let stick = Vec2::new(
+                stick_value(GamepadAxisType::LeftStickX),
+                stick_value(GamepadAxisType::LeftStickY),
+            );
+
+            if stick == Vec2::ZERO {
+                MoveInput::Digital(digital_movement)
+            } else {
+                MoveInput::Analog(stick.clamp_length_max(1.0))
+            }
+        }
+        InputDevice::KeyboardMouse => match settings.control_scheme {
+            ControlScheme::Mouse => MoveInput::Cursor(game_info.cursor_position),
+            ControlScheme::Keyboard => MoveInput::Digital(digital_movement),
+        },
+        // Player follows the finger, and stops when it's lifted
+        InputDevice::Touch => match game_info.touch_position {
+            Some(touch_position) => MoveInput::Cursor(touch_position),
+            None => MoveInput::Digital(Vec2::ZERO),
+        },
+    };
+}
@@ -0,0 +1,229 @@
+pub mod resources;
+mod systems;
+
+use super::enemy::EnemyType;
+use super::hazards::HazardKind;
+use super::pickups::PickupKind;
+use super::{GameState, MAX_DEPTH};
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+// Backgrounds, particles and music stems of two biomes are mixed for this long
+const BIOME_CROSSFADE_DURATION: f32 = 3.0;
+// Between the background and the game
+const AMBIENT_PARTICLE_Z: f32 = -0.5;
+// Every particle gets a bit different speed and size
+const AMBIENT_PARTICLE_VARIATION: f32 = 0.3;
+
+pub const BIOMES: [Biome; 5] = [
+    Biome {
+        name: "Topsoil",
+        start_depth: 0.0,
+        endless_only: false,
+        background_layers: ["sprites/bg1.png", "sprites/bg2.png"],
+        palette: Color::Hsla {
+            hue: 360.0,
+            saturation: 0.0,
+            lightness: 0.5,
+            alpha: 1.0,
+        },
+        particles: AmbientParticles {
+            color: Color::rgb(0.75, 0.65, 0.5),
+            size: 3.0,
+            spawn_rate: 4.0,
+            velocity: Vec2::new(5.0, 25.0),
+            lifetime: 4.0,
+        },
+        music_stem: None,
+        enemy_pool: &[(EnemyType::Follower, 1.0)],
+        hazard_pool: &[],
+        pickup_pool: &[(PickupKind::Shield, 0.5), (PickupKind::Chrono, 0.5)],
+    },
+    Biome {
+        name: "Root Tangle",
+        start_depth: 50.0,
+        endless_only: false,
+        background_layers: ["sprites/bg2.png", "sprites/bg1.png"],
+        palette: Color::Hsla {
+            hue: 30.0,
+            saturation: 0.35,
+            lightness: 0.45,
+            alpha: 1.0,
+        },
+        particles: AmbientParticles {
+            color: Color::rgb(0.55, 0.4, 0.25),
+            size: 4.0,
+            spawn_rate: 5.0,
+            velocity: Vec2::new(-10.0, 35.0),
+            lifetime: 4.0,
+        },
+        music_stem: None,
+        enemy_pool: &[
+            (EnemyType::Follower, 0.35),
+            (EnemyType::Shooter, 0.45),
+            (EnemyType::Shielded, 0.2),
+        ],
+        hazard_pool: &[(HazardKind::RockOutcrop, 1.0)],
+        pickup_pool: &[],
+    },
+    Biome {
+        name: "Fungal Hollow",
+        start_depth: 100.0,
+        endless_only: false,
+        background_layers: ["sprites/bg1.png", "sprites/bg2.png"],
+        palette: Color::Hsla {
+            hue: 150.0,
+            saturation: 0.35,
+            lightness: 0.4,
+            alpha: 1.0,
+        },
+        particles: AmbientParticles {
+            color: Color::rgb(0.55, 0.95, 0.6),
+            size: 3.0,
+            spawn_rate: 8.0,
+            velocity: Vec2::new(0.0, 12.0),
+            lifetime: 6.0,
+        },
+        music_stem: None,
+        enemy_pool: &[
+            (EnemyType::Follower, 0.3),
+            (EnemyType::Shooter, 0.25),
+            (EnemyType::Shielded, 0.15),
+            (EnemyType::Charger, 0.2),
+            (EnemyType::Splitter, 0.1),
+        ],
+        hazard_pool: &[
+            (HazardKind::RockOutcrop, 0.6),
+            (HazardKind::SpinningBlade, 0.4),
+        ],
+        pickup_pool: &[(PickupKind::Coolant, 1.5), (PickupKind::Magnet, 1.5)],
+    },
+    Biome {
+        name: "Heartwood",
+        start_depth: 150.0,
+        endless_only: false,
+        background_layers: ["sprites/bg2.png", "sprites/bg1.png"],
+        palette: Color::Hsla {
+            hue: 15.0,
+            saturation: 0.55,
+            lightness: 0.35,
+            alpha: 1.0,
+        },
+        particles: AmbientParticles {
+            color: Color::rgb(1.0, 0.55, 0.15),
+            size: 2.5,
+            spawn_rate: 10.0,
+            velocity: Vec2::new(0.0, 60.0),
+            lifetime: 2.5,
+        },
+        music_stem: None,
+        enemy_pool: &[
+            (EnemyType::Follower, 0.2),
+            (EnemyType::Shooter, 0.25),
+            (EnemyType::Shielded, 0.15),
+            (EnemyType::Charger, 0.2),
+            (EnemyType::Splitter, 0.2),
+        ],
+        hazard_pool: &[
+            (HazardKind::RockOutcrop, 0.4),
+            (HazardKind::SpinningBlade, 0.3),
+            (HazardKind::ElectrifiedCable, 0.3),
+        ],
+        pickup_pool: &[
+            (PickupKind::Health, 1.5),
+            (PickupKind::Shield, 1.5),
+            (PickupKind::Coolant, 2.0),
+        ],
+    },
+    // Standard mode reaches MAX_DEPTH at the boss fight, which stays in the previous biome
+    Biome {
+        name: "Abyss",
+        start_depth: MAX_DEPTH,
+        endless_only: true,
+        background_layers: ["sprites/bg1.png", "sprites/bg2.png"],
+        palette: Color::Hsla {
+            hue: 240.0,
+            saturation: 0.4,
+            lightness: 0.25,
+            alpha: 1.0,
+        },
+        particles: AmbientParticles {
+            color: Color::rgb(0.45, 0.55, 1.0),
+            size: 2.0,
+            spawn_rate: 6.0,
+            velocity: Vec2::new(0.0, 80.0),
+            lifetime: 3.0,
+        },
+        music_stem: None,
+        enemy_pool: &[
+            (EnemyType::Follower, 0.15),
+            (EnemyType::Shooter, 0.2),
+            (EnemyType::Shielded, 0.2),
+            (EnemyType::Charger, 0.2),
+            (EnemyType::Splitter, 0.25),
+        ],
+        hazard_pool: &[
+            (HazardKind::RockOutcrop, 0.3),
+            (HazardKind::SpinningBlade, 0.35),
+            (HazardKind::ElectrifiedCable, 0.35),
+        ],
+        pickup_pool: &[
+            (PickupKind::Health, 1.5),
+            (PickupKind::Shield, 2.0),
+            (PickupKind::Chrono, 2.0),
+        ],
+    },
+];
+
+pub struct BiomesPlugin;
+
+impl Plugin for BiomesPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<BiomeState>()
+            .init_resource::<BiomeMusic>()
+            .add_systems(
+                (
+                    update_biome,
+                    spawn_ambient_particles.after(update_biome),
+                    mix_biome_music.after(update_biome),
+                )
+                    .in_set(OnUpdate(GameState::Running)),
+            );
+    }
+}
+
+// Depth range with its own look, sound and enemies
+pub struct Biome {
+    // Only for reading the table, nothing shows it yet
+    #[allow(dead_code)]
+    pub name: &'static str,
+    // Biome lasts until the start of the next one
+    pub start_depth: f32,
+    pub endless_only: bool,
+    // Top and bottom parallax layers
+    pub background_layers: [&'static str; 2],
+    // Tint of the background layers
+    pub palette: Color,
+    pub particles: AmbientParticles,
+    // Looped on top of the main music. No stems are recorded yet.
+    pub music_stem: Option<&'static str>,
+    // Regular enemies with their spawn weights. Every enemy still waits for its depth level,
+    // the boss comes by the depth as well.
+    pub enemy_pool: &'static [(EnemyType, f32)],
+    // Hazards with their spawn weights, none in the first biome
+    pub hazard_pool: &'static [(HazardKind, f32)],
+    // Multipliers of the pickup spawn weights, the pickups not listed keep their own weight
+    pub pickup_pool: &'static [(PickupKind, f32)],
+}
+
+// Dust, spores and embers floating over the background
+pub struct AmbientParticles {
+    pub color: Color,
+    pub size: f32,
+    // Particles per second
+    pub spawn_rate: f32,
+    pub velocity: Vec2,
+    pub lifetime: f32,
+}
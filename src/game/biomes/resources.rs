@@ -0,0 +1,54 @@
+use super::{Biome, BIOMES, BIOME_CROSSFADE_DURATION};
+
+use bevy::prelude::*;
+
+#[derive(Resource)]
+pub struct BiomeState {
+    // Indices in BIOMES
+    pub current: usize,
+    pub previous: usize,
+    // Current biome fades in over the previous one
+    pub crossfade_timer: Timer,
+}
+
+impl Default for BiomeState {
+    fn default() -> Self {
+        let mut crossfade_timer = Timer::from_seconds(BIOME_CROSSFADE_DURATION, TimerMode::Once);
+        crossfade_timer.set_elapsed(crossfade_timer.duration());
+
+        BiomeState {
+            current: 0,
+            previous: 0,
+            crossfade_timer,
+        }
+    }
+}
+
+impl BiomeState {
+    pub fn biome(&self) -> &'static Biome {
+        &BIOMES[self.current]
+    }
+
+    pub fn is_crossfading(&self) -> bool {
+        !self.crossfade_timer.finished()
+    }
+
+    // How much of the biome is heard and seen right now, from 0 to 1
+    pub fn weight(&self, biome_index: usize) -> f32 {
+        let crossfade = self.crossfade_timer.percent();
+
+        if biome_index == self.current {
+            crossfade
+        } else if biome_index == self.previous {
+            1.0 - crossfade
+        } else {
+            0.0
+        }
+    }
+}
+
+// Playing music stems, one slot per biome
+#[derive(Resource, Default)]
+pub struct BiomeMusic {
+    pub stems: [Option<Handle<AudioSink>>; BIOMES.len()],
+}
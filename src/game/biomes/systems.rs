@@ -0,0 +1,116 @@
+use super::resources::{BiomeMusic, BiomeState};
+use super::{AMBIENT_PARTICLE_VARIATION, AMBIENT_PARTICLE_Z, BIOMES};
+use crate::game::{ArenaBounds, GameInfo};
+use crate::particle_system::components::Particle;
+use crate::settings::resources::GameSettings;
+use crate::settings::GameMode;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+pub fn update_biome(
+    mut biome_state: ResMut<BiomeState>,
+    game_info: Res<GameInfo>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let is_endless = settings.game_mode == GameMode::Endless;
+    let biome_index = BIOMES
+        .iter()
+        .rposition(|biome| {
+            game_info.player_progress >= biome.start_depth && (is_endless || !biome.endless_only)
+        })
+        .unwrap_or(0);
+
+    if biome_index != biome_state.current {
+        biome_state.previous = biome_state.current;
+        biome_state.current = biome_index;
+        biome_state.crossfade_timer.reset();
+    }
+
+    biome_state.crossfade_timer.tick(time.delta());
+}
+
+// During the crossfade both biomes spawn their particles, fewer of them
+pub fn spawn_ambient_particles(
+    mut commands: Commands,
+    biome_state: Res<BiomeState>,
+    arena_bounds: Res<ArenaBounds>,
+    time: Res<Time>,
+) {
+    let mut rng = thread_rng();
+
+    for (biome_index, biome) in BIOMES.iter().enumerate() {
+        let particles = &biome.particles;
+        let spawn_chance =
+            particles.spawn_rate * biome_state.weight(biome_index) * time.delta_seconds();
+
+        if rng.gen::<f32>() >= spawn_chance {
+            continue;
+        }
+
+        let variation =
+            1.0 + rng.gen_range(-AMBIENT_PARTICLE_VARIATION..AMBIENT_PARTICLE_VARIATION);
+        let position = Vec3::new(
+            arena_bounds.random_x(Vec2::ZERO, &mut rng),
+            rng.gen_range(arena_bounds.min.y..arena_bounds.max.y),
+            AMBIENT_PARTICLE_Z,
+        );
+
+        commands.spawn((
+            SpriteBundle {
+                transform: Transform::from_translation(position),
+                sprite: Sprite {
+                    color: particles.color.with_a(0.0),
+                    custom_size: Some(Vec2::splat(particles.size * variation)),
+                    ..default()
+                },
+                ..default()
+            },
+            Particle::new(
+                particles.velocity * variation,
+                particles.lifetime,
+                particles.color.a(),
+            ),
+        ));
+    }
+}
+
+// Stems start silently, when their biome fades in, and stop, when it has faded out
+pub fn mix_biome_music(
+    mut biome_music: ResMut<BiomeMusic>,
+    biome_state: Res<BiomeState>,
+    audio: Res<Audio>,
+    audio_sinks: Res<Assets<AudioSink>>,
+    asset_server: Res<AssetServer>,
+) {
+    for (biome_index, biome) in BIOMES.iter().enumerate() {
+        let Some(music_stem) = biome.music_stem else {
+            continue;
+        };
+
+        let weight = biome_state.weight(biome_index);
+        let stem_sink = &mut biome_music.stems[biome_index];
+
+        if weight > 0.0 && stem_sink.is_none() {
+            let weak_handle = audio.play_with_settings(
+                asset_server.load(music_stem),
+                PlaybackSettings::LOOP.with_volume(0.0),
+            );
+            *stem_sink = Some(audio_sinks.get_handle(weak_handle));
+        }
+
+        let Some(stem_handle) = stem_sink.clone() else {
+            continue;
+        };
+
+        if let Some(sink) = audio_sinks.get(&stem_handle) {
+            sink.set_volume(weight);
+
+            if weight <= 0.0 {
+                sink.stop();
+                *stem_sink = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,36 @@
+pub mod resources;
+mod systems;
+
+use super::GameState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+// Time for the next hit, before the combo starts to decay
+const COMBO_WINDOW: f32 = 3.0;
+// Every few hits raise the multiplier by a step
+const COMBO_HITS_PER_STEP: usize = 5;
+const COMBO_MULTIPLIER_STEP: f32 = 0.5;
+const COMBO_MAX_MULTIPLIER: f32 = 4.0;
+// Hits lost every window without a hit, so the multiplier drops by a step at a time
+const COMBO_DECAY_HITS: usize = COMBO_HITS_PER_STEP;
+
+// Score for every point of the killed enemy's max health
+pub const SCORE_PER_ENEMY_HP: f32 = 10.0;
+// Score for every meter of depth, the kills come on top of it
+pub const SCORE_PER_DEPTH: usize = 10;
+
+pub struct ComboPlugin;
+
+impl Plugin for ComboPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Combo>().add_systems(
+            (
+                tick_combo_timer,
+                handle_combo_hit_event.after(tick_combo_timer),
+            )
+                .in_set(OnUpdate(GameState::Running)),
+        );
+    }
+}
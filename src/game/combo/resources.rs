@@ -0,0 +1,45 @@
+use super::{COMBO_DECAY_HITS, COMBO_HITS_PER_STEP, COMBO_MAX_MULTIPLIER};
+use super::{COMBO_MULTIPLIER_STEP, COMBO_WINDOW};
+
+use bevy::prelude::*;
+
+// Consecutive kills, reflects and ignitions, which multiply the blood and score gain
+#[derive(Resource)]
+pub struct Combo {
+    pub count: usize,
+    pub highest: usize,
+    // Combo decays, every time it finishes
+    pub window_timer: Timer,
+}
+
+impl Default for Combo {
+    fn default() -> Self {
+        Combo {
+            count: 0,
+            highest: 0,
+            window_timer: Timer::from_seconds(COMBO_WINDOW, TimerMode::Once),
+        }
+    }
+}
+
+impl Combo {
+    pub fn add_hit(&mut self) {
+        self.count += 1;
+        self.highest = self.highest.max(self.count);
+        self.window_timer.reset();
+    }
+
+    pub fn decay(&mut self) {
+        self.count = self.count.saturating_sub(COMBO_DECAY_HITS);
+        self.window_timer.reset();
+    }
+
+    pub fn break_combo(&mut self) {
+        self.count = 0;
+    }
+
+    pub fn multiplier(&self) -> f32 {
+        (1.0 + (self.count / COMBO_HITS_PER_STEP) as f32 * COMBO_MULTIPLIER_STEP)
+            .min(COMBO_MAX_MULTIPLIER)
+    }
+}
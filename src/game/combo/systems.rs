@@ -0,0 +1,26 @@
+use super::resources::Combo;
+use crate::game::events::ComboHitEvent;
+
+use bevy::prelude::*;
+
+// Combo loses a multiplier step for every window, in which nothing happens
+pub fn tick_combo_timer(mut combo: ResMut<Combo>, time: Res<Time>) {
+    if combo.count == 0 {
+        return;
+    }
+
+    combo.window_timer.tick(time.delta());
+
+    if combo.window_timer.finished() {
+        combo.decay();
+    }
+}
+
+pub fn handle_combo_hit_event(
+    mut combo_hit_event_reader: EventReader<ComboHitEvent>,
+    mut combo: ResMut<Combo>,
+) {
+    for _ in combo_hit_event_reader.iter() {
+        combo.add_hit();
+    }
+}
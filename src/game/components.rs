@@ -1,8 +1,13 @@
+use super::pickups::PickupKind;
+use super::Faction;
+
 use bevy::prelude::*;
 
 #[derive(Component, Default)]
 pub struct ParallaxBackground {
     pub size: Vec2,
+    // Index in BIOMES
+    pub biome: usize,
 }
 
 #[derive(Component)]
@@ -16,17 +21,75 @@ pub struct MainCamera;
 #[derive(Component)]
 pub struct Pickup {
     pub collider: Collider,
+    pub kind: PickupKind,
+}
+
+#[derive(Component)]
+pub struct Projectile {
+    pub speed: f32,
+    pub direction: Vec3,
+    pub collider: Collider,
+    pub faction: Faction,
+    // Damage to enemies, after the chainsaw reflects the projectile
+    pub reflect_damage: f32,
+    // Stray projectiles are despawned, when it finishes
+    pub lifetime: Timer,
 }
 
+// Changes projectile's speed from start to end over the duration
 #[derive(Component)]
-pub struct FuelPickup;
+pub struct ProjectileSpeedCurve {
+    pub start: f32,
+    pub end: f32,
+    pub timer: Timer,
+}
 
+// Turns the projectile toward the player
 #[derive(Component)]
-pub struct HealthPickup;
+pub struct Homing {
+    // Radians per second
+    pub turn_rate: f32,
+}
 
+// Projectile bounces off the arena edges instead of leaving the arena
 #[derive(Component)]
-pub struct Projectile {
-    pub speed: f32,
+pub struct Bouncing {
+    pub bounces_left: usize,
+}
+
+// Projectile wiggles sideways around its path
+#[derive(Component)]
+pub struct SineMotion {
+    pub amplitude: f32,
+    // Oscillations per second
+    pub frequency: f32,
+    pub elapsed: f32,
+    // Sideways offset applied in the last frame
+    pub offset: f32,
+}
+
+// Beam, which only warns the player while charging and hurts when it fires
+#[derive(Component)]
+pub struct Laser {
     pub direction: Vec3,
-    pub collider: Collider,
+    pub length: f32,
+    pub width: f32,
+    pub charge_timer: Timer,
+    pub fire_timer: Timer,
+}
+
+impl Laser {
+    pub fn is_firing(&self) -> bool {
+        self.charge_timer.finished()
+    }
+
+    // Checks the distance from the beam to the box center, good enough for our colliders
+    pub fn hits(&self, origin: Vec3, position: Vec3, size: Vec2) -> bool {
+        let to_position = (position - origin).truncate();
+        let direction = self.direction.truncate();
+        let along = to_position.dot(direction).clamp(0.0, self.length);
+        let distance = to_position.distance(direction * along);
+
+        distance <= (self.width + size.min_element()) / 2.0
+    }
 }
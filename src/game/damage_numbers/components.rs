@@ -0,0 +1,37 @@
+use super::{DamageNumberKind, DAMAGE_NUMBER_LIFETIME, DAMAGE_NUMBER_MERGE_WINDOW};
+
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct DamageNumber {
+    // None, when the popup is back in the pool
+    pub kind: Option<DamageNumberKind>,
+    pub target: Option<Entity>,
+    // Last known position of the target
+    pub anchor: Vec3,
+    pub amount: f32,
+    // Restarted with every merged hit, so the number rises only after the hits stop
+    pub lifetime: Timer,
+}
+
+impl Default for DamageNumber {
+    fn default() -> Self {
+        DamageNumber {
+            kind: None,
+            target: None,
+            anchor: Vec3::ZERO,
+            amount: 0.0,
+            lifetime: Timer::from_seconds(DAMAGE_NUMBER_LIFETIME, TimerMode::Once),
+        }
+    }
+}
+
+impl DamageNumber {
+    // Another hit on the same target, which is still adding up
+    pub fn can_merge(&self, target: Option<Entity>, kind: DamageNumberKind) -> bool {
+        target.is_some()
+            && self.target == target
+            && self.kind == Some(kind)
+            && self.lifetime.elapsed_secs() < DAMAGE_NUMBER_MERGE_WINDOW
+    }
+}
@@ -0,0 +1,90 @@
+pub mod components;
+mod resources;
+mod systems;
+
+use super::GameState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+// Popups are spawned once and reused
+const DAMAGE_NUMBER_POOL_SIZE: usize = 48;
+const DAMAGE_NUMBER_FONT: &str = "fonts/origami_mommy_regular.ttf";
+const DAMAGE_NUMBER_FONT_SIZE: f32 = 22.0;
+const DAMAGE_NUMBER_CRITICAL_FONT_SIZE: f32 = 30.0;
+// Above the sprites
+const DAMAGE_NUMBER_Z: f32 = 10.0;
+const DAMAGE_NUMBER_LIFETIME: f32 = 0.8;
+const DAMAGE_NUMBER_RISE_SPEED: f32 = 40.0;
+// Gap between the target and the number
+const DAMAGE_NUMBER_OFFSET: Vec2 = Vec2::new(0.0, 24.0);
+// Hits on the same target within this time add up to the shown number
+const DAMAGE_NUMBER_MERGE_WINDOW: f32 = 0.25;
+// Critical hits pop up bigger and shrink back
+const DAMAGE_NUMBER_CRITICAL_POP_SCALE: f32 = 1.5;
+const DAMAGE_NUMBER_CRITICAL_POP_DURATION: f32 = 0.15;
+
+const DAMAGE_NUMBER_NORMAL_COLOR: Color = Color::WHITE;
+const DAMAGE_NUMBER_CRITICAL_COLOR: Color = Color::rgb(1.0, 0.85, 0.2);
+const DAMAGE_NUMBER_BURN_COLOR: Color = Color::rgb(1.0, 0.5, 0.15);
+const DAMAGE_NUMBER_BLEED_COLOR: Color = Color::rgb(0.75, 0.1, 0.1);
+const DAMAGE_NUMBER_HEAL_COLOR: Color = Color::rgb(0.3, 0.9, 0.3);
+
+pub struct DamageNumbersPlugin;
+
+impl Plugin for DamageNumbersPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<DamageNumberPool>()
+            .add_startup_system(spawn_damage_number_pool)
+            .add_systems(
+                (
+                    handle_damage_number_event,
+                    animate_damage_numbers.after(handle_damage_number_event),
+                )
+                    .in_set(OnUpdate(GameState::Running)),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DamageNumberKind {
+    Normal,
+    Critical,
+    Burn,
+    Bleed,
+    Heal,
+}
+
+impl DamageNumberKind {
+    pub fn color(&self) -> Color {
+        match self {
+            DamageNumberKind::Normal => DAMAGE_NUMBER_NORMAL_COLOR,
+            DamageNumberKind::Critical => DAMAGE_NUMBER_CRITICAL_COLOR,
+            DamageNumberKind::Burn => DAMAGE_NUMBER_BURN_COLOR,
+            DamageNumberKind::Bleed => DAMAGE_NUMBER_BLEED_COLOR,
+            DamageNumberKind::Heal => DAMAGE_NUMBER_HEAL_COLOR,
+        }
+    }
+
+    pub fn font_size(&self) -> f32 {
+        match self {
+            DamageNumberKind::Critical => DAMAGE_NUMBER_CRITICAL_FONT_SIZE,
+            _ => DAMAGE_NUMBER_FONT_SIZE,
+        }
+    }
+
+    // Small chainsaw ticks would otherwise show up as zeros
+    pub fn format(&self, amount: f32) -> String {
+        let value = if amount < 10.0 {
+            format!("{:.1}", amount)
+        } else {
+            format!("{:.0}", amount)
+        };
+
+        match self {
+            DamageNumberKind::Heal => format!("+{}", value),
+            _ => value,
+        }
+    }
+}
@@ -0,0 +1,7 @@
+use bevy::prelude::*;
+
+// Hidden popups, which are ready to be shown
+#[derive(Resource, Default)]
+pub struct DamageNumberPool {
+    pub free: Vec<Entity>,
+}
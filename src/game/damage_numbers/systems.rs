@@ -0,0 +1,141 @@
+use super::components::DamageNumber;
+use super::resources::DamageNumberPool;
+use super::*;
+use crate::game::events::DamageNumberEvent;
+
+use bevy::prelude::*;
+
+pub fn spawn_damage_number_pool(
+    mut commands: Commands,
+    mut damage_number_pool: ResMut<DamageNumberPool>,
+    asset_server: Res<AssetServer>,
+) {
+    for _ in 0..DAMAGE_NUMBER_POOL_SIZE {
+        let damage_number_entity = commands
+            .spawn((
+                Text2dBundle {
+                    text: Text::from_section(
+                        "",
+                        TextStyle {
+                            font: asset_server.load(DAMAGE_NUMBER_FONT),
+                            font_size: DAMAGE_NUMBER_FONT_SIZE,
+                            color: DAMAGE_NUMBER_NORMAL_COLOR,
+                        },
+                    )
+                    .with_alignment(TextAlignment::Center),
+                    visibility: Visibility::Hidden,
+                    ..default()
+                },
+                DamageNumber::default(),
+            ))
+            .id();
+
+        damage_number_pool.free.push(damage_number_entity);
+    }
+}
+
+// Adds the hit to the number still shown over the target, or takes a new one from the pool
+pub fn handle_damage_number_event(
+    mut damage_number_event_reader: EventReader<DamageNumberEvent>,
+    mut damage_numbers_query: Query<(Entity, &mut DamageNumber, &mut Text, &mut Visibility)>,
+    mut damage_number_pool: ResMut<DamageNumberPool>,
+) {
+    for event in damage_number_event_reader.iter() {
+        if let Some((_, mut damage_number, mut text, _)) = damage_numbers_query
+            .iter_mut()
+            .find(|(_, damage_number, _, _)| damage_number.can_merge(event.target, event.kind))
+        {
+            damage_number.amount += event.amount;
+            damage_number.anchor = event.position;
+            damage_number.lifetime.reset();
+            text.sections[0].value = event.kind.format(damage_number.amount);
+            continue;
+        }
+
+        // When all the numbers are in use, the oldest one is reused
+        let damage_number_entity = damage_number_pool.free.pop().or_else(|| {
+            damage_numbers_query
+                .iter()
+                .max_by(|(_, a, _, _), (_, b, _, _)| {
+                    a.lifetime
+                        .elapsed_secs()
+                        .total_cmp(&b.lifetime.elapsed_secs())
+                })
+                .map(|(entity, _, _, _)| entity)
+        });
+
+        let Some(Ok((_, mut damage_number, mut text, mut visibility))) =
+            damage_number_entity.map(|entity| damage_numbers_query.get_mut(entity))
+        else {
+            continue;
+        };
+
+        damage_number.kind = Some(event.kind);
+        damage_number.target = event.target;
+        damage_number.anchor = event.position;
+        damage_number.amount = event.amount;
+        damage_number.lifetime.reset();
+
+        text.sections[0].value = event.kind.format(event.amount);
+        text.sections[0].style.color = event.kind.color();
+        text.sections[0].style.font_size = event.kind.font_size();
+        *visibility = Visibility::Inherited;
+    }
+}
+
+// Numbers follow their targets, rise and fade out, then go back to the pool
+pub fn animate_damage_numbers(
+    mut damage_numbers_query: Query<(
+        Entity,
+        &mut DamageNumber,
+        &mut Text,
+        &mut Transform,
+        &mut Visibility,
+    )>,
+    targets_query: Query<&Transform, Without<DamageNumber>>,
+    mut damage_number_pool: ResMut<DamageNumberPool>,
+    time: Res<Time>,
+) {
+    for (entity, mut damage_number, mut text, mut transform, mut visibility) in
+        damage_numbers_query.iter_mut()
+    {
+        let Some(kind) = damage_number.kind else {
+            continue;
+        };
+
+        damage_number.lifetime.tick(time.delta());
+
+        if damage_number.lifetime.finished() {
+            damage_number.kind = None;
+            damage_number.target = None;
+            *visibility = Visibility::Hidden;
+            damage_number_pool.free.push(entity);
+            continue;
+        }
+
+        if let Some(target_transform) = damage_number
+            .target
+            .and_then(|target| targets_query.get(target).ok())
+        {
+            damage_number.anchor = target_transform.translation;
+        }
+
+        let elapsed = damage_number.lifetime.elapsed_secs();
+        let rise = DAMAGE_NUMBER_RISE_SPEED * elapsed;
+
+        transform.translation =
+            (damage_number.anchor.truncate() + DAMAGE_NUMBER_OFFSET + Vec2::new(0.0, rise))
+                .extend(DAMAGE_NUMBER_Z);
+
+        transform.scale = if kind == DamageNumberKind::Critical {
+            let pop = (1.0 - elapsed / DAMAGE_NUMBER_CRITICAL_POP_DURATION).max(0.0);
+            Vec3::splat(1.0 + (DAMAGE_NUMBER_CRITICAL_POP_SCALE - 1.0) * pop)
+        } else {
+            Vec3::ONE
+        };
+
+        // Fades out during the second half of the lifetime
+        let alpha = (2.0 * (1.0 - damage_number.lifetime.percent())).min(1.0);
+        text.sections[0].style.color.set_a(alpha);
+    }
+}
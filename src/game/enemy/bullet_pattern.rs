@@ -0,0 +1,166 @@
+use bevy::asset::{AssetLoader, Error, LoadContext, LoadedAsset};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::Deserialize;
+
+// Pattern files, which are loaded on startup, so they are ready when an enemy fires
+pub const BULLET_PATTERN_PATHS: [&str; 7] = [
+    SHOOTER_BULLET_PATTERN,
+    BOSS_FAN_BULLET_PATTERN,
+    BOSS_RADIAL_BULLET_PATTERN,
+    BOSS_SPIRAL_BULLET_PATTERN,
+    BOSS_HOMING_BULLET_PATTERN,
+    BOSS_WAVE_BULLET_PATTERN,
+    BOSS_LASER_BULLET_PATTERN,
+];
+pub const SHOOTER_BULLET_PATTERN: &str = "patterns/shooter.pattern.ron";
+pub const BOSS_FAN_BULLET_PATTERN: &str = "patterns/boss_fan.pattern.ron";
+pub const BOSS_RADIAL_BULLET_PATTERN: &str = "patterns/boss_radial.pattern.ron";
+pub const BOSS_SPIRAL_BULLET_PATTERN: &str = "patterns/boss_spiral.pattern.ron";
+pub const BOSS_HOMING_BULLET_PATTERN: &str = "patterns/boss_homing.pattern.ron";
+pub const BOSS_WAVE_BULLET_PATTERN: &str = "patterns/boss_wave.pattern.ron";
+pub const BOSS_LASER_BULLET_PATTERN: &str = "patterns/boss_laser.pattern.ron";
+
+// Used, when the pattern doesn't set them
+const DEFAULT_PROJECTILE_SIZE: f32 = 39.2;
+const DEFAULT_PROJECTILE_COLLIDER_SIZE: f32 = 32.0;
+const DEFAULT_PROJECTILE_LIFETIME: f32 = 8.0;
+const DEFAULT_REFLECT_DAMAGE: f32 = 5.0;
+
+// Describes one volley of projectiles. Angles are in degrees.
+#[derive(Deserialize, TypeUuid, Debug, Clone)]
+#[uuid = "5d3f0a7e-2b8c-4f6e-9a41-c7e2d8b6f013"]
+pub struct BulletPattern {
+    // Projectiles fired at once, spread evenly over the spread angle
+    pub emitters: usize,
+    // 360 and more spreads the emitters around the full circle
+    pub spread: f32,
+    // Degrees per second, used by the spinning aim
+    #[serde(default)]
+    pub rotation_speed: f32,
+    // Shots in one volley
+    #[serde(default = "default_bursts")]
+    pub bursts: usize,
+    // Seconds between the shots of the volley
+    #[serde(default)]
+    pub shot_delay: f32,
+    pub speed: ProjectileSpeed,
+    pub aim: AimMode,
+    #[serde(default = "default_projectile_size")]
+    pub projectile_size: f32,
+    #[serde(default = "default_collider_size")]
+    pub collider_size: f32,
+    // Damage to enemies, after the chainsaw reflects the projectile
+    #[serde(default = "default_reflect_damage")]
+    pub reflect_damage: f32,
+    // Seconds before the projectile disappears
+    #[serde(default = "default_lifetime")]
+    pub lifetime: f32,
+    // Degrees per second, how fast projectiles turn to the player
+    #[serde(default)]
+    pub homing: Option<f32>,
+    // How many times projectiles bounce off the arena edges
+    #[serde(default)]
+    pub bounces: usize,
+    #[serde(default)]
+    pub sine: Option<SineWave>,
+    // Fires laser beams instead of projectiles
+    #[serde(default)]
+    pub laser: Option<LaserBeam>,
+}
+
+fn default_bursts() -> usize {
+    1
+}
+
+fn default_projectile_size() -> f32 {
+    DEFAULT_PROJECTILE_SIZE
+}
+
+fn default_collider_size() -> f32 {
+    DEFAULT_PROJECTILE_COLLIDER_SIZE
+}
+
+fn default_reflect_damage() -> f32 {
+    DEFAULT_REFLECT_DAMAGE
+}
+
+fn default_lifetime() -> f32 {
+    DEFAULT_PROJECTILE_LIFETIME
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct SineWave {
+    pub amplitude: f32,
+    // Oscillations per second
+    pub frequency: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct LaserBeam {
+    pub length: f32,
+    pub width: f32,
+    // Seconds of the warning line before the beam
+    pub charge_duration: f32,
+    pub fire_duration: f32,
+}
+
+// Projectile speed goes from start to end during the duration
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct ProjectileSpeed {
+    pub start: f32,
+    #[serde(default)]
+    pub end: Option<f32>,
+    #[serde(default)]
+    pub duration: f32,
+}
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub enum AimMode {
+    // Middle of the spread points at the player
+    Player,
+    // Fixed angle, 0 points to the right
+    Fixed(f32),
+    // Keeps rotating with the rotation speed, even between the volleys
+    Spinning,
+}
+
+impl BulletPattern {
+    // Angles (in radians) of the projectiles in one shot
+    pub fn shot_angles(&self, base_angle: f32) -> Vec<f32> {
+        let spread = self.spread.to_radians();
+
+        (0..self.emitters)
+            .map(|i| {
+                if self.emitters == 1 {
+                    base_angle
+                } else if spread >= std::f32::consts::TAU {
+                    base_angle + i as f32 * std::f32::consts::TAU / self.emitters as f32
+                } else {
+                    base_angle - spread / 2.0 + i as f32 * spread / (self.emitters - 1) as f32
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Default)]
+pub struct BulletPatternLoader;
+
+impl AssetLoader for BulletPatternLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), Error>> {
+        Box::pin(async move {
+            let bullet_pattern = ron::de::from_bytes::<BulletPattern>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(bullet_pattern));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["pattern.ron"]
+    }
+}
@@ -1,4 +1,9 @@
-use super::{EnemyType, FIRE_DURATION};
+use super::bullet_pattern::BulletPattern;
+use super::{
+    EnemyType, BOSS_PHASES, BOSS_PHASE_TRANSITION_DURATION, ENEMY_HEALTH_BAR_FADE_DURATION,
+    ENEMY_HEALTH_BAR_SHOW_DURATION, SHIELDED_BACKSTAB_DAMAGE_MULTIPLIER, SHIELDED_BACK_ARC,
+    SHIELDED_SHIELD_ARC,
+};
 use crate::game::components::Collider;
 
 use bevy::prelude::*;
@@ -21,34 +26,86 @@ pub struct Enemy {
     pub destination: Vec3,
 
     pub destination_reached: bool,
+}
 
-    // Used when the enemy is on fire and it becomes reddish
-    pub is_green_decreasing: bool,
+#[derive(Debug, Default, PartialEq)]
+pub enum EnemyState {
+    #[default]
+    Spawned,
+    Engaging,
 }
 
 #[derive(Component)]
-pub struct FireTimer {
-    pub timer: Timer,
+pub struct FollowAI;
+
+// Background of the health bar over the enemy, the fill is its child
+#[derive(Component)]
+pub struct EnemyHealthBar {
+    // Health at the last update, the bar shows up again when it drops
+    pub shown_hp: f32,
+    pub visible_timer: Timer,
 }
 
-impl Default for FireTimer {
-    fn default() -> Self {
-        FireTimer {
-            timer: Timer::from_seconds(FIRE_DURATION, TimerMode::Once),
+impl EnemyHealthBar {
+    pub fn new(hp: f32) -> Self {
+        let mut visible_timer = Timer::from_seconds(
+            ENEMY_HEALTH_BAR_SHOW_DURATION + ENEMY_HEALTH_BAR_FADE_DURATION,
+            TimerMode::Once,
+        );
+        // Hidden until the first hit
+        visible_timer.set_elapsed(visible_timer.duration());
+
+        EnemyHealthBar {
+            shown_hp: hp,
+            visible_timer,
         }
     }
+
+    pub fn alpha(&self) -> f32 {
+        let fade_time = self.visible_timer.elapsed_secs() - ENEMY_HEALTH_BAR_SHOW_DURATION;
+
+        (1.0 - fade_time / ENEMY_HEALTH_BAR_FADE_DURATION).clamp(0.0, 1.0)
+    }
 }
 
-#[derive(Debug, Default, PartialEq)]
-pub enum EnemyState {
-    #[default]
-    Spawned,
-    Engaging,
-    OnFire,
+#[derive(Component)]
+pub struct EnemyHealthBarFill;
+
+// Push from a hit, which fades out. Enemies flying this way are hurt by the hazards.
+#[derive(Component)]
+pub struct EnemyKnockback {
+    pub velocity: Vec3,
 }
 
+// Fires volleys of the bullet pattern. Enemy AI only decides when to start one.
 #[derive(Component)]
-pub struct FollowAI;
+pub struct BulletEmitter {
+    pub pattern: Handle<BulletPattern>,
+    // New volley starts, as soon as the pattern is loaded
+    pub volley_requested: bool,
+    // Shots left in the current volley
+    pub shots_left: usize,
+    pub shot_timer: Timer,
+    // Radians, used by the spinning aim
+    pub spin_angle: f32,
+}
+
+impl BulletEmitter {
+    pub fn new(pattern: Handle<BulletPattern>) -> Self {
+        BulletEmitter {
+            pattern,
+            volley_requested: false,
+            shots_left: 0,
+            shot_timer: Timer::default(),
+            spin_angle: 0.0,
+        }
+    }
+
+    // Starts a new volley, the unfinished one is interrupted
+    pub fn start_volley(&mut self) {
+        self.volley_requested = true;
+    }
+}
 
 #[derive(Component)]
 pub struct ShooterAI {
@@ -56,3 +113,97 @@ pub struct ShooterAI {
     pub reload_speed: f32,
     pub reload_timer: Timer,
 }
+
+// Stops, telegraphs the attack with a line toward the player and dashes along it.
+// Gets stunned when it crashes into the arena edge.
+#[derive(Component, Default)]
+pub struct ChargerAI {
+    pub state: ChargerState,
+    pub dash_direction: Vec3,
+    // Duration of the current state. Stalking lasts until the player comes close.
+    pub state_timer: Timer,
+}
+
+#[derive(Debug, Default, PartialEq, Clone, Copy)]
+pub enum ChargerState {
+    #[default]
+    Stalking,
+    WindingUp,
+    Dashing,
+    Recovering,
+}
+
+// Line, which shows where the charger is about to dash
+#[derive(Component)]
+pub struct ChargerWindUpLine;
+
+// Splits into smaller copies of itself on death
+#[derive(Component)]
+pub struct Splitter {
+    // 0 for the splitter spawned by the spawner, +1 for every split
+    pub generation: u32,
+}
+
+// Chainsaw can't cut through the front, while the back takes extra damage
+#[derive(Component)]
+pub struct Shielded {
+    pub facing: Vec3,
+}
+
+impl Shielded {
+    // Damage multiplier for the hit from the given direction, None if the shield deflects it
+    pub fn hit_multiplier(&self, direction_to_attacker: Vec3) -> Option<f32> {
+        let angle = self.facing.angle_between(direction_to_attacker);
+
+        if angle <= SHIELDED_SHIELD_ARC / 2.0 {
+            None
+        } else if angle >= std::f32::consts::PI - SHIELDED_BACK_ARC / 2.0 {
+            Some(SHIELDED_BACKSTAB_DAMAGE_MULTIPLIER)
+        } else {
+            Some(1.0)
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Shield;
+
+#[derive(Component)]
+pub struct BossAI {
+    // Index in BOSS_PHASES
+    pub phase: usize,
+    // Index in the attacks of the current phase
+    pub attack_index: usize,
+    pub attack_timer: Timer,
+    pub invulnerability_timer: Timer,
+    // Time spent in the current phase, drives the movement patterns
+    pub phase_time: f32,
+    pub is_diving: bool,
+    // Bosses of the endless mode attack faster every time
+    pub attack_period_multiplier: f32,
+}
+
+impl BossAI {
+    pub fn new(attack_period_multiplier: f32) -> Self {
+        let mut invulnerability_timer =
+            Timer::from_seconds(BOSS_PHASE_TRANSITION_DURATION, TimerMode::Once);
+        invulnerability_timer.set_elapsed(invulnerability_timer.duration());
+
+        BossAI {
+            phase: 0,
+            attack_index: 0,
+            attack_timer: Timer::from_seconds(
+                BOSS_PHASES[0].attack_period * attack_period_multiplier,
+                TimerMode::Repeating,
+            ),
+            invulnerability_timer,
+            phase_time: 0.0,
+            is_diving: false,
+            attack_period_multiplier,
+        }
+    }
+
+    pub fn is_invulnerable(&self) -> bool {
+        !self.invulnerability_timer.finished()
+    }
+}
@@ -1,3 +1,4 @@
+pub mod bullet_pattern;
 pub mod components;
 mod resources;
 pub mod systems;
@@ -5,10 +6,12 @@ pub mod systems;
 use std::ops::Range;
 
 use crate::game::GameState;
+use bullet_pattern::*;
 use resources::*;
 use systems::*;
 
 use bevy::prelude::*;
+use serde::Serialize;
 
 // === Follower ===
 const FOLLOWER_MOVEMENT_SPEED: f32 = 132.6;
@@ -23,27 +26,160 @@ const SHOOTER_RELOAD_SPEED: f32 = 1.0;
 const SHOOTER_DISTANCE_FROM_PLAYER: f32 = 10.0;
 const SHOOTER_COLLIDER_SIZE: Vec2 = Vec2::new(62.0, 57.0);
 const SHOOTER_DEPTH_LEVEL: f32 = 50.0;
-const SHOOTER_PROJECTILE_SPEED: f32 = 180.0;
-const SHOOTER_PROJECTILE_SIZE: Vec2 = Vec2::new(39.2, 39.2);
-const SHOOTER_PROJECTILE_COLLIDER_SIZE: Vec2 = Vec2::new(32., 32.);
+
+// === Charger ===
+const CHARGER_MOVEMENT_SPEED: f32 = 95.0;
+const CHARGER_HEALTH: f32 = 12.5;
+const CHARGER_SPRITE_SIZE: Vec2 = Vec2::new(90.0 * 1.25, 30.0 * 1.25);
+const CHARGER_COLLIDER_SIZE: Vec2 = Vec2::new(23.0 * 1.25, 26.0 * 1.25);
+const CHARGER_DEPTH_LEVEL: f32 = 100.0;
+// Starts winding up, when the player is closer than this
+const CHARGER_ATTACK_DISTANCE: f32 = 240.0;
+const CHARGER_WIND_UP_DURATION: f32 = 0.8;
+const CHARGER_DASH_DURATION: f32 = 0.6;
+const CHARGER_DASH_SPEED_MULTIPLIER: f32 = 5.0;
+const CHARGER_RECOVERY_DURATION: f32 = 1.0;
+const CHARGER_WIND_UP_LINE_LENGTH: f32 = 240.0;
+const CHARGER_WIND_UP_LINE_WIDTH: f32 = 3.0;
+const CHARGER_WIND_UP_LINE_COLOR: Color = Color::rgba(1.0, 0.2, 0.2, 0.75);
+
+// === Splitter ===
+const SPLITTER_MOVEMENT_SPEED: f32 = 70.0;
+const SPLITTER_HEALTH: f32 = 15.0;
+const SPLITTER_SPRITE_SIZE: Vec2 = Vec2::new(90.0 * 1.5, 30.0 * 1.5);
+const SPLITTER_COLLIDER_SIZE: Vec2 = Vec2::new(23.0 * 1.5, 26.0 * 1.5);
+const SPLITTER_DEPTH_LEVEL: f32 = 130.0;
+const SPLITTER_CHILDREN_NUM: usize = 3;
+// Children of the last generation don't split anymore
+const SPLITTER_MAX_GENERATION: u32 = 1;
+// Health and size of every next generation are multiplied by this
+const SPLITTER_CHILD_SCALE: f32 = 0.55;
+const SPLITTER_CHILD_SPEED_MULTIPLIER: f32 = 1.4;
+const SPLITTER_SCATTER_DISTANCE: f32 = 70.0;
+
+// === Shielded ===
+const SHIELDED_MOVEMENT_SPEED: f32 = 60.0;
+const SHIELDED_HEALTH: f32 = 12.5;
+const SHIELDED_COLLIDER_SIZE: Vec2 = Vec2::new(23.0 * 1.2, 26.0 * 1.2);
+const SHIELDED_DEPTH_LEVEL: f32 = 80.0;
+// Radians per second, slow enough to walk around it
+const SHIELDED_TURN_SPEED: f32 = 1.6;
+// Full angles of the covered front and the exposed back
+const SHIELDED_SHIELD_ARC: f32 = 2.1;
+const SHIELDED_BACK_ARC: f32 = 1.6;
+const SHIELDED_BACKSTAB_DAMAGE_MULTIPLIER: f32 = 2.5;
+const SHIELDED_SHIELD_SIZE: Vec2 = Vec2::new(6.0, 46.0);
+const SHIELDED_SHIELD_OFFSET: f32 = 26.0;
+const SHIELDED_SHIELD_COLOR: Color = Color::rgb(0.55, 0.75, 1.0);
 
 // === Boss ===
+pub const BOSS_NAME: &str = "The Lumberlord";
 const BOSS_MOVEMENT_SPEED: f32 = 50.0;
 const BOSS_HEALTH: f32 = 300.;
-const BOSS_RELOAD_SPEED: f32 = 0.75;
-const BOSS_DISTANCE_FROM_PLAYER: f32 = 10.0;
 const BOSS_SPRITE_SIZE: Vec2 = Vec2::new(65. * 3., 61. * 3.);
 const BOSS_COLLIDER_SIZE: Vec2 = Vec2::new(62.0 * 3., 57.0 * 3.);
 const BOSS_DEPTH_LEVEL: f32 = 200.;
-const BOSS_PROJECTILE_SPEED: f32 = 360.0;
-const BOSS_PROJECTILE_SIZE: Vec2 = Vec2::new(39.2 * 2., 39.2 * 2.);
-const BOSS_PROJECTILE_COLLIDER_SIZE: Vec2 = Vec2::new(32. * 2., 32. * 2.);
+// Boss can't be damaged and doesn't attack while changing the phase
+const BOSS_PHASE_TRANSITION_DURATION: f32 = 1.5;
+const BOSS_PHASE_TRANSITION_FLICKER_SPEED: f32 = 25.0;
+// Hover point is below the arena center, so the player has room above the boss
+const BOSS_HOVER_OFFSET: Vec2 = Vec2::new(0.0, -120.0);
+const BOSS_HOVER_AMPLITUDE: Vec2 = Vec2::new(70.0, 30.0);
+const BOSS_HOVER_FREQUENCY: f32 = 0.8;
+const BOSS_SUMMON_FOLLOWERS_NUM: usize = 2;
+// In the endless mode the boss comes back this much deeper after every kill,
+// with more health and speed, and attacking faster
+const ENDLESS_BOSS_INTERVAL: f32 = 150.0;
+const ENDLESS_BOSS_HEALTH_GROWTH: f32 = 0.5;
+const ENDLESS_BOSS_SPEED_GROWTH: f32 = 0.1;
+const ENDLESS_BOSS_ATTACK_PERIOD_DECAY: f32 = 0.85;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossMovement {
+    // Floats around the hover point
+    Hover,
+    // Goes from one side of the arena to the other
+    Sweep,
+    // Rushes to the player and returns to the hover point
+    Dive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BossAttack {
+    // Path to the bullet pattern file
+    Pattern(&'static str),
+    SummonFollowers,
+}
+
+pub struct BossPhase {
+    // Phase starts, when the boss' health fraction drops to this value
+    pub health_threshold: f32,
+    pub movement: BossMovement,
+    pub speed_multiplier: f32,
+    // Attacks are used one by one in a loop
+    pub attacks: &'static [BossAttack],
+    pub attack_period: f32,
+    pub sprite: &'static str,
+}
 
-const FIRE_DURATION: f32 = 4.0;
-const ENEMY_ON_FIRE_SPEED_GAIN: f32 = 50.0;
-const FIRE_COLOR_SPEED: f32 = 17.5;
-const FIRE_FLASH_GREEN_MIN: f32 = 100.0;
-const FIRE_FLASH_GREEN_MAX: f32 = 200.0;
+pub const BOSS_PHASES: [BossPhase; 3] = [
+    BossPhase {
+        health_threshold: 1.0,
+        movement: BossMovement::Hover,
+        speed_multiplier: 1.0,
+        attacks: &[
+            BossAttack::Pattern(BOSS_FAN_BULLET_PATTERN),
+            BossAttack::Pattern(BOSS_HOMING_BULLET_PATTERN),
+            BossAttack::Pattern(BOSS_RADIAL_BULLET_PATTERN),
+        ],
+        attack_period: 1.1,
+        sprite: "sprites/boss_phase_1.png",
+    },
+    BossPhase {
+        health_threshold: 0.66,
+        movement: BossMovement::Sweep,
+        speed_multiplier: 3.0,
+        attacks: &[
+            BossAttack::Pattern(BOSS_RADIAL_BULLET_PATTERN),
+            BossAttack::Pattern(BOSS_LASER_BULLET_PATTERN),
+            BossAttack::SummonFollowers,
+        ],
+        attack_period: 0.9,
+        sprite: "sprites/boss_phase_2.png",
+    },
+    BossPhase {
+        health_threshold: 0.33,
+        movement: BossMovement::Dive,
+        speed_multiplier: 4.0,
+        attacks: &[
+            BossAttack::Pattern(BOSS_SPIRAL_BULLET_PATTERN),
+            BossAttack::Pattern(BOSS_WAVE_BULLET_PATTERN),
+        ],
+        attack_period: 1.2,
+        sprite: "sprites/boss_phase_3.png",
+    },
+];
+
+// === Health bars ===
+const ENEMY_HEALTH_BAR_SIZE: Vec2 = Vec2::new(36.0, 5.0);
+// Gap between the collider top and the bar
+const ENEMY_HEALTH_BAR_MARGIN: f32 = 8.0;
+// Bar is fully shown for a while after the last hit, then fades out
+const ENEMY_HEALTH_BAR_SHOW_DURATION: f32 = 2.0;
+const ENEMY_HEALTH_BAR_FADE_DURATION: f32 = 0.5;
+const ENEMY_HEALTH_BAR_BACKGROUND_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+const ENEMY_HEALTH_BAR_FILL_COLOR: Color = Color::rgb(0.85, 0.15, 0.15);
+
+// === Knockback ===
+// Reflected projectiles and fire waves push the enemies, the boss stays in place
+const REFLECTED_PROJECTILE_KNOCKBACK_SPEED: f32 = 350.0;
+const FIRE_WAVE_KNOCKBACK_SPEED: f32 = 550.0;
+const ENEMY_KNOCKBACK_DAMPING: f32 = 6.0;
+// Knockback ends below this speed
+const ENEMY_KNOCKBACK_MIN_SPEED: f32 = 40.0;
+
+// Repeating timers can't have zero duration
+const MIN_SHOT_DELAY: f32 = 0.01;
 
 const ENEMY_SPAWN_PERIOD: f32 = 3.5;
 const ENEMY_RANGE_SPEED: Range<f32> = 0.85..1.;
@@ -52,28 +188,78 @@ pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<EnemySpawnTimer>().add_systems(
-            (
-                tick_enemy_spawn_timer,
-                tick_enemy_fire_timer,
-                tick_shooter_reloading_timer,
-                handle_on_fire_state,
-                spawn_enemies_over_time,
-                handle_enemy_take_damage_event,
-                move_enemies_to_destination,
-                follow_player,
-                handle_shooter_ai,
-                handle_fire_wave_event,
-                limit_enemy_movement.after(move_enemies_to_destination),
+        app.init_resource::<EnemySpawnTimer>()
+            .init_resource::<BossEncounters>()
+            .add_asset::<BulletPattern>()
+            .init_asset_loader::<BulletPatternLoader>()
+            .add_startup_system(load_bullet_patterns)
+            .add_system(
+                apply_difficulty_to_enemy_spawn_timer.in_schedule(OnExit(GameState::MainMenu)),
+            )
+            .add_systems(
+                (
+                    tick_enemy_spawn_timer,
+                    tick_shooter_reloading_timer,
+                    spawn_enemies_over_time,
+                    handle_enemy_take_damage_event,
+                    move_enemies_to_destination,
+                    follow_player,
+                    handle_shooter_ai,
+                    handle_boss_ai.before(move_enemies_to_destination),
+                    handle_charger_ai.before(move_enemies_to_destination),
+                    turn_shielded_enemies,
+                    // Knockback is inserted before the killed enemies are despawned
+                    handle_fire_wave_event.before(handle_enemy_take_damage_event),
+                    apply_enemy_knockback.after(move_enemies_to_destination),
+                    limit_enemy_movement
+                        .after(move_enemies_to_destination)
+                        .after(apply_enemy_knockback),
+                )
+                    .in_set(OnUpdate(GameState::Running)),
             )
-                .in_set(OnUpdate(GameState::Running)),
-        );
+            // Projectiles fired by the enemies and reflected back at them
+            .add_systems(
+                (
+                    fire_bullet_emitters
+                        .after(handle_shooter_ai)
+                        .after(handle_boss_ai),
+                    check_reflected_projectile_collision.before(handle_enemy_take_damage_event),
+                )
+                    .in_set(OnUpdate(GameState::Running)),
+            )
+            // Difficulty scaling and health bars over the damaged enemies (boss has its own in the HUD)
+            .add_systems(
+                (
+                    apply_difficulty_to_new_enemies,
+                    ramp_enemy_spawn_timer.before(tick_enemy_spawn_timer),
+                    attach_enemy_health_bars.after(apply_difficulty_to_new_enemies),
+                    update_enemy_health_bars.after(handle_enemy_take_damage_event),
+                )
+                    .in_set(OnUpdate(GameState::Running)),
+            );
     }
 }
 
-#[derive(PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
 pub enum EnemyType {
     Follower,
     Shooter,
+    Charger,
+    Splitter,
+    Shielded,
     Boss,
 }
+
+impl EnemyType {
+    // Depth, from which the enemy can spawn
+    pub fn depth_level(&self) -> f32 {
+        match self {
+            EnemyType::Follower => FOLLOWER_DEPTH_LEVEL,
+            EnemyType::Shooter => SHOOTER_DEPTH_LEVEL,
+            EnemyType::Charger => CHARGER_DEPTH_LEVEL,
+            EnemyType::Splitter => SPLITTER_DEPTH_LEVEL,
+            EnemyType::Shielded => SHIELDED_DEPTH_LEVEL,
+            EnemyType::Boss => BOSS_DEPTH_LEVEL,
+        }
+    }
+}
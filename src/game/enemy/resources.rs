@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 
-use super::ENEMY_SPAWN_PERIOD;
+use super::bullet_pattern::BulletPattern;
+use super::{BOSS_DEPTH_LEVEL, ENEMY_SPAWN_PERIOD};
 
 #[derive(Resource)]
 pub struct EnemySpawnTimer {
@@ -14,3 +15,28 @@ impl Default for EnemySpawnTimer {
         }
     }
 }
+
+// Bosses met during the run, only the endless mode has more than one
+#[derive(Resource)]
+pub struct BossEncounters {
+    // Depth where the next boss spawns
+    pub next_depth: f32,
+    pub killed: u32,
+}
+
+impl Default for BossEncounters {
+    fn default() -> Self {
+        BossEncounters {
+            next_depth: BOSS_DEPTH_LEVEL,
+            killed: 0,
+        }
+    }
+}
+
+// Keeps the bullet patterns loaded for the whole game
+#[derive(Resource, Default)]
+pub struct BulletPatternHandles {
+    // Never read, only holds the strong handles
+    #[allow(dead_code)]
+    pub handles: Vec<Handle<BulletPattern>>,
+}
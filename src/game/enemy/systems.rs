@@ -1,38 +1,56 @@
+use std::time::Duration;
+
 use super::components::*;
-use super::resources::EnemySpawnTimer;
+use super::resources::{BossEncounters, BulletPatternHandles, EnemySpawnTimer};
 use super::*;
-use crate::game::components::{Collider, Projectile};
-use crate::game::events::{ChainsawFireWave, EnemyTakeDamageEvent};
+use crate::game::biomes::resources::BiomeState;
+use crate::game::combo::resources::Combo;
+use crate::game::combo::SCORE_PER_ENEMY_HP;
+use crate::game::components::{
+    Bouncing, Collider, Homing, Laser, Projectile, ProjectileSpeedCurve, SineMotion,
+};
+use crate::game::damage_numbers::DamageNumberKind;
+use crate::game::events::{
+    ChainsawFireWave, ComboHitEvent, DamageNumberEvent, DamageSource, EnemyKilledEvent,
+    EnemyTakeDamageEvent,
+};
 use crate::game::player::components::Player;
+use crate::game::player::resources::PlayerInfo;
 use crate::game::player::{
-    PlayerState, CHAINSAW_ENEMY_SLOW_DOWN_FACTOR, PLAYER_CHAINSAW_COLLIDER_SIZE, PLAYER_DAMAGE,
-    PLAYER_DAMAGE_SPEED,
+    PlayerState, CHAINSAW_ENEMY_SLOW_DOWN_FACTOR, PLAYER_CHAINSAW_COLLIDER_SIZE,
 };
-use crate::game::{GameInfo, MAX_ENEMIES_NUM};
+use crate::game::status_effects::components::StatusEffects;
+use crate::game::status_effects::StatusEffectKind;
+use crate::game::{endless_ramp, endless_spawn_rate_multiplier, endless_speed_multiplier};
+use crate::game::{ArenaBounds, Faction, GameInfo, RunRng, ARENA_SIZE};
+use crate::settings::difficulty::DifficultyProfile;
+use crate::settings::resources::GameSettings;
+use crate::settings::GameMode;
 
 use bevy::prelude::*;
-use bevy::window::PrimaryWindow;
+use bevy::sprite::collide_aabb::collide;
+use bevy::sprite::Anchor;
 use rand::prelude::*;
 
-// TODO: fix bug when enemy can leave borders of the window when running from the player
-
 // Spawn enemies outside the bottom border of the screen
 // And set them random direction in direction from the bottom to the arena.
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_enemies_over_time(
     mut commands: Commands,
     mut game_info: ResMut<GameInfo>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena_bounds: Res<ArenaBounds>,
     asset_server: Res<AssetServer>,
     enemy_timer: Res<EnemySpawnTimer>,
+    boss_encounters: Res<BossEncounters>,
+    biome_state: Res<BiomeState>,
+    mut run_rng: ResMut<RunRng>,
 ) {
     if enemy_timer.timer.just_finished() {
-        if game_info.enemies_num >= MAX_ENEMIES_NUM || game_info.is_boss_spawned {
+        if game_info.enemies_num >= game_info.max_enemies_num || game_info.is_boss_spawned {
             return;
         }
 
-        let primary_window = window_query.get_single().unwrap();
-
-        let mut rng = thread_rng();
+        let rng = &mut *run_rng;
 
         let player_progress = game_info.player_progress;
         let enemies_num = game_info.enemies_num;
@@ -40,16 +58,18 @@ pub fn spawn_enemies_over_time(
         game_info.enemies_num += 1;
         game_info
             .enemies_spawn_queue
-            .push_back(if player_progress >= BOSS_DEPTH_LEVEL {
+            .push_back(if player_progress >= boss_encounters.next_depth {
                 EnemyType::Boss
-            } else if player_progress >= SHOOTER_DEPTH_LEVEL {
-                if rng.gen::<f32>() > 0.4 {
-                    EnemyType::Shooter
-                } else {
-                    EnemyType::Follower
-                }
             } else {
-                EnemyType::Follower
+                // Regular enemies come from the pool of the current biome, once deep enough
+                biome_state
+                    .biome()
+                    .enemy_pool
+                    .iter()
+                    .filter(|(enemy_type, _)| player_progress >= enemy_type.depth_level())
+                    .collect::<Vec<_>>()
+                    .choose_weighted(rng, |(_, weight)| *weight)
+                    .map_or(EnemyType::Follower, |(enemy_type, _)| *enemy_type)
             });
 
         if game_info.enemies_spawn_queue.back() == Some(&EnemyType::Boss) {
@@ -61,13 +81,15 @@ pub fn spawn_enemies_over_time(
             let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED) * SHOOTER_MOVEMENT_SPEED;
 
             let enemy_starting_position = Vec3::new(
-                rng.gen::<f32>() * (primary_window.width() - SHOOTER_COLLIDER_SIZE.x),
-                0.0 - SHOOTER_COLLIDER_SIZE.y,
+                arena_bounds.random_x(SHOOTER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y - SHOOTER_COLLIDER_SIZE.y,
                 0.0,
             );
             let enemy_destination = Vec3::new(
-                rng.gen::<f32>() * (primary_window.width() - SHOOTER_COLLIDER_SIZE.x),
-                0.0 + SHOOTER_COLLIDER_SIZE.y + rng.gen::<f32>() * SHOOTER_COLLIDER_SIZE.y,
+                arena_bounds.random_x(SHOOTER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y
+                    + SHOOTER_COLLIDER_SIZE.y
+                    + rng.gen::<f32>() * SHOOTER_COLLIDER_SIZE.y,
                 0.0,
             );
             let enemy_direction = (enemy_destination - enemy_starting_position).normalize();
@@ -97,83 +119,222 @@ pub fn spawn_enemies_over_time(
                     destination: enemy_destination,
 
                     destination_reached: false,
-                    is_green_decreasing: false,
                 },
-                FireTimer::default(),
+                StatusEffects::default(),
                 ShooterAI {
                     reload_speed: SHOOTER_RELOAD_SPEED,
                     max_distance_from_player: SHOOTER_DISTANCE_FROM_PLAYER,
                     reload_timer: Timer::from_seconds(SHOOTER_RELOAD_SPEED, TimerMode::Once),
                 },
+                BulletEmitter::new(asset_server.load(SHOOTER_BULLET_PATTERN)),
             ));
         }
         // === Follower ===
         else if game_info.enemies_spawn_queue.front().unwrap() == &EnemyType::Follower {
-            let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED) * FOLLOWER_MOVEMENT_SPEED;
+            let enemy_starting_position = Vec3::new(
+                arena_bounds.random_x(FOLLOWER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y - FOLLOWER_COLLIDER_SIZE.y,
+                0.0,
+            );
+            let enemy_destination = Vec3::new(
+                arena_bounds.random_x(FOLLOWER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y
+                    + FOLLOWER_COLLIDER_SIZE.y
+                    + rng.gen::<f32>() * FOLLOWER_COLLIDER_SIZE.y,
+                0.0,
+            );
+
+            spawn_follower(
+                &mut commands,
+                &asset_server,
+                rng,
+                enemy_starting_position,
+                enemy_destination,
+            );
+        }
+        // === Charger ===
+        else if game_info.enemies_spawn_queue.front().unwrap() == &EnemyType::Charger {
+            let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED) * CHARGER_MOVEMENT_SPEED;
 
             let enemy_starting_position = Vec3::new(
-                rng.gen::<f32>() * (primary_window.width() - FOLLOWER_COLLIDER_SIZE.x),
-                0.0 - FOLLOWER_COLLIDER_SIZE.y,
+                arena_bounds.random_x(CHARGER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y - CHARGER_COLLIDER_SIZE.y,
                 0.0,
             );
             let enemy_destination = Vec3::new(
-                rng.gen::<f32>() * (primary_window.width() - FOLLOWER_COLLIDER_SIZE.x),
-                0.0 + FOLLOWER_COLLIDER_SIZE.y + rng.gen::<f32>() * FOLLOWER_COLLIDER_SIZE.y,
+                arena_bounds.random_x(CHARGER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y
+                    + CHARGER_COLLIDER_SIZE.y
+                    + rng.gen::<f32>() * CHARGER_COLLIDER_SIZE.y,
                 0.0,
             );
             let enemy_direction = (enemy_destination - enemy_starting_position).normalize();
 
-            commands.spawn((
-                SpriteBundle {
-                    transform: Transform::from_translation(enemy_starting_position),
-                    texture: asset_server.load("sprites/follower_default.png"),
-                    ..default()
-                },
-                Enemy {
-                    max_hp: FOLLOWER_HEALTH,
-                    current_hp: FOLLOWER_HEALTH,
+            commands
+                .spawn((
+                    SpriteBundle {
+                        transform: Transform::from_translation(enemy_starting_position),
+                        texture: asset_server.load("sprites/follower_default.png"),
+                        sprite: Sprite {
+                            custom_size: Some(CHARGER_SPRITE_SIZE),
+                            ..default()
+                        },
+                        ..default()
+                    },
+                    Enemy {
+                        max_hp: CHARGER_HEALTH,
+                        current_hp: CHARGER_HEALTH,
 
-                    current_speed: speed,
-                    default_speed: speed,
+                        current_speed: speed,
+                        default_speed: speed,
 
-                    enemy_type: EnemyType::Follower,
-                    depth_level: FOLLOWER_DEPTH_LEVEL,
+                        enemy_type: EnemyType::Charger,
+                        depth_level: CHARGER_DEPTH_LEVEL,
 
-                    collider: Collider {
-                        size: FOLLOWER_COLLIDER_SIZE,
-                    },
-                    state: EnemyState::Spawned,
+                        collider: Collider {
+                            size: CHARGER_COLLIDER_SIZE,
+                        },
+                        state: EnemyState::Spawned,
 
-                    direction: enemy_direction,
-                    destination: enemy_destination,
+                        direction: enemy_direction,
+                        destination: enemy_destination,
 
-                    destination_reached: false,
-                    is_green_decreasing: false,
-                },
-                FireTimer::default(),
-                FollowAI {},
-            ));
+                        destination_reached: false,
+                    },
+                    StatusEffects::default(),
+                    ChargerAI::default(),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        SpriteBundle {
+                            // Behind the charger itself
+                            transform: Transform::from_xyz(0.0, 0.0, -0.5),
+                            sprite: Sprite {
+                                color: CHARGER_WIND_UP_LINE_COLOR,
+                                custom_size: Some(Vec2::new(
+                                    CHARGER_WIND_UP_LINE_LENGTH,
+                                    CHARGER_WIND_UP_LINE_WIDTH,
+                                )),
+                                anchor: Anchor::CenterLeft,
+                                ..default()
+                            },
+                            visibility: Visibility::Hidden,
+                            ..default()
+                        },
+                        ChargerWindUpLine,
+                    ));
+                });
         }
-        // === Boss ===
-        else if game_info.enemies_spawn_queue.front().unwrap() == &EnemyType::Boss {
-            let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED) * BOSS_MOVEMENT_SPEED;
+        // === Splitter ===
+        else if game_info.enemies_spawn_queue.front().unwrap() == &EnemyType::Splitter {
+            let enemy_starting_position = Vec3::new(
+                arena_bounds.random_x(SPLITTER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y - SPLITTER_COLLIDER_SIZE.y,
+                0.0,
+            );
+            let enemy_destination = Vec3::new(
+                arena_bounds.random_x(SPLITTER_COLLIDER_SIZE, rng),
+                arena_bounds.min.y
+                    + SPLITTER_COLLIDER_SIZE.y
+                    + rng.gen::<f32>() * SPLITTER_COLLIDER_SIZE.y,
+                0.0,
+            );
+
+            spawn_splitter(
+                &mut commands,
+                &asset_server,
+                rng,
+                enemy_starting_position,
+                enemy_destination,
+                0,
+            );
+        }
+        // === Shielded ===
+        else if game_info.enemies_spawn_queue.front().unwrap() == &EnemyType::Shielded {
+            let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED) * SHIELDED_MOVEMENT_SPEED;
 
             let enemy_starting_position = Vec3::new(
-                primary_window.width() / 2.0,
-                0.0 - BOSS_COLLIDER_SIZE.y,
+                arena_bounds.random_x(SHIELDED_COLLIDER_SIZE, rng),
+                arena_bounds.min.y - SHIELDED_COLLIDER_SIZE.y,
                 0.0,
             );
             let enemy_destination = Vec3::new(
-                primary_window.width() / 2.0,
-                primary_window.height() / 2.0,
+                arena_bounds.random_x(SHIELDED_COLLIDER_SIZE, rng),
+                arena_bounds.min.y
+                    + SHIELDED_COLLIDER_SIZE.y
+                    + rng.gen::<f32>() * SHIELDED_COLLIDER_SIZE.y,
+                0.0,
+            );
+            let enemy_direction = (enemy_destination - enemy_starting_position).normalize();
+
+            commands
+                .spawn((
+                    SpriteBundle {
+                        transform: Transform::from_translation(enemy_starting_position),
+                        texture: asset_server.load("sprites/follower_default.png"),
+                        ..default()
+                    },
+                    Enemy {
+                        max_hp: SHIELDED_HEALTH,
+                        current_hp: SHIELDED_HEALTH,
+
+                        current_speed: speed,
+                        default_speed: speed,
+
+                        enemy_type: EnemyType::Shielded,
+                        depth_level: SHIELDED_DEPTH_LEVEL,
+
+                        collider: Collider {
+                            size: SHIELDED_COLLIDER_SIZE,
+                        },
+                        state: EnemyState::Spawned,
+
+                        direction: enemy_direction,
+                        destination: enemy_destination,
+
+                        destination_reached: false,
+                    },
+                    StatusEffects::default(),
+                    FollowAI {},
+                    Shielded {
+                        facing: enemy_direction,
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        SpriteBundle {
+                            sprite: Sprite {
+                                color: SHIELDED_SHIELD_COLOR,
+                                custom_size: Some(SHIELDED_SHIELD_SIZE),
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        Shield,
+                    ));
+                });
+        }
+        // === Boss ===
+        else if game_info.enemies_spawn_queue.front().unwrap() == &EnemyType::Boss {
+            // Every boss of the endless mode is stronger than the previous one
+            let encounter = boss_encounters.killed as f32;
+            let health = BOSS_HEALTH * (1.0 + ENDLESS_BOSS_HEALTH_GROWTH * encounter);
+            let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED)
+                * BOSS_MOVEMENT_SPEED
+                * (1.0 + ENDLESS_BOSS_SPEED_GROWTH * encounter);
+
+            let enemy_starting_position = Vec3::new(
+                arena_bounds.center().x,
+                arena_bounds.min.y - BOSS_COLLIDER_SIZE.y,
                 0.0,
             );
+            let enemy_destination = arena_bounds.center().extend(0.0);
             let enemy_direction = (enemy_destination - enemy_starting_position).normalize();
 
             commands.spawn((
                 SpriteBundle {
                     transform: Transform::from_translation(enemy_starting_position),
-                    texture: asset_server.load("sprites/shooter_default.png"),
+                    texture: asset_server.load(BOSS_PHASES[0].sprite),
                     sprite: Sprite {
                         custom_size: Some(BOSS_SPRITE_SIZE),
                         ..default()
@@ -181,8 +342,8 @@ pub fn spawn_enemies_over_time(
                     ..default()
                 },
                 Enemy {
-                    max_hp: BOSS_HEALTH,
-                    current_hp: BOSS_HEALTH,
+                    max_hp: health,
+                    current_hp: health,
 
                     current_speed: speed,
                     default_speed: speed,
@@ -199,14 +360,10 @@ pub fn spawn_enemies_over_time(
                     destination: enemy_destination,
 
                     destination_reached: false,
-                    is_green_decreasing: false,
-                },
-                FireTimer::default(),
-                ShooterAI {
-                    max_distance_from_player: BOSS_DISTANCE_FROM_PLAYER,
-                    reload_speed: BOSS_RELOAD_SPEED,
-                    reload_timer: Timer::from_seconds(BOSS_RELOAD_SPEED, TimerMode::Once),
                 },
+                StatusEffects::default(),
+                BossAI::new(ENDLESS_BOSS_ATTACK_PERIOD_DECAY.powf(encounter)),
+                BulletEmitter::new(asset_server.load(BOSS_FAN_BULLET_PATTERN)),
             ));
         }
 
@@ -214,34 +371,131 @@ pub fn spawn_enemies_over_time(
     }
 }
 
+pub fn spawn_follower(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    rng: &mut RunRng,
+    position: Vec3,
+    destination: Vec3,
+) {
+    let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED) * FOLLOWER_MOVEMENT_SPEED;
+
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(position),
+            texture: asset_server.load("sprites/follower_default.png"),
+            ..default()
+        },
+        Enemy {
+            max_hp: FOLLOWER_HEALTH,
+            current_hp: FOLLOWER_HEALTH,
+
+            current_speed: speed,
+            default_speed: speed,
+
+            enemy_type: EnemyType::Follower,
+            depth_level: FOLLOWER_DEPTH_LEVEL,
+
+            collider: Collider {
+                size: FOLLOWER_COLLIDER_SIZE,
+            },
+            state: EnemyState::Spawned,
+
+            direction: (destination - position).normalize_or_zero(),
+            destination,
+
+            destination_reached: false,
+        },
+        StatusEffects::default(),
+        FollowAI {},
+    ));
+}
+
+// Splitters of every next generation are smaller, weaker and faster
+pub fn spawn_splitter(
+    commands: &mut Commands,
+    asset_server: &AssetServer,
+    rng: &mut RunRng,
+    position: Vec3,
+    destination: Vec3,
+    generation: u32,
+) {
+    let scale = SPLITTER_CHILD_SCALE.powi(generation as i32);
+    let speed: f32 = rng.gen_range(ENEMY_RANGE_SPEED)
+        * SPLITTER_MOVEMENT_SPEED
+        * SPLITTER_CHILD_SPEED_MULTIPLIER.powi(generation as i32);
+    let health = SPLITTER_HEALTH * scale;
+
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_translation(position),
+            texture: asset_server.load("sprites/follower_default.png"),
+            sprite: Sprite {
+                custom_size: Some(SPLITTER_SPRITE_SIZE * scale),
+                flip_y: true,
+                ..default()
+            },
+            ..default()
+        },
+        Enemy {
+            max_hp: health,
+            current_hp: health,
+
+            current_speed: speed,
+            default_speed: speed,
+
+            enemy_type: EnemyType::Splitter,
+            depth_level: SPLITTER_DEPTH_LEVEL,
+
+            collider: Collider {
+                size: SPLITTER_COLLIDER_SIZE * scale,
+            },
+            state: EnemyState::Spawned,
+
+            direction: (destination - position).normalize_or_zero(),
+            destination,
+
+            destination_reached: false,
+        },
+        StatusEffects::default(),
+        FollowAI {},
+        Splitter { generation },
+    ));
+}
+
 // Upon spawning, enemies will slowly move to the arena outside of the screen.
 // When they reach the destination, enemy transitions to ENGAGING state.
 // Move enemies to destination using their direction.
 // When they reach destination, set destination_reached to true.
 pub fn move_enemies_to_destination(
-    mut enemies_query: Query<(&mut Transform, &mut Enemy)>,
+    mut enemies_query: Query<(&mut Transform, &mut Enemy, &StatusEffects)>,
     player_state: Res<State<PlayerState>>,
     time: Res<Time>,
 ) {
-    for (mut enemy_transform, mut enemy_struct) in enemies_query.iter_mut() {
+    for (mut enemy_transform, mut enemy_struct, status_effects) in enemies_query.iter_mut() {
         if enemy_struct.destination_reached && enemy_struct.state == EnemyState::Spawned {
             enemy_struct.state = EnemyState::Engaging;
         }
 
-        if enemy_struct.state == EnemyState::OnFire {
+        let speed = status_effects.modify_speed(enemy_struct.current_speed);
+
+        if status_effects.is_panicking() {
             enemy_struct.destination_reached = false;
-            enemy_transform.translation +=
-                enemy_struct.direction * time.delta_seconds() * enemy_struct.current_speed;
+            enemy_transform.translation += enemy_struct.direction * time.delta_seconds() * speed;
             continue;
         }
 
-        if enemy_struct.enemy_type == EnemyType::Follower && player_state.0 == PlayerState::CHAINSAW
+        let direction = if enemy_struct.enemy_type == EnemyType::Follower
+            && player_state.0 == PlayerState::CHAINSAW
         {
-            enemy_struct.direction =
-                (enemy_transform.translation - enemy_struct.destination).normalize();
+            enemy_transform.translation - enemy_struct.destination
         } else {
-            enemy_struct.direction =
-                (enemy_struct.destination - enemy_transform.translation).normalize();
+            enemy_struct.destination - enemy_transform.translation
+        };
+
+        // Standing enemies keep their last direction, so they know where to run when ignited
+        if let Some(direction) = direction.try_normalize() {
+            enemy_struct.direction = direction;
         }
 
         if enemy_transform
@@ -250,64 +504,52 @@ pub fn move_enemies_to_destination(
             > 1.0
         {
             enemy_struct.destination_reached = false;
-            enemy_transform.translation +=
-                enemy_struct.direction * time.delta_seconds() * enemy_struct.current_speed;
+            enemy_transform.translation += enemy_struct.direction * time.delta_seconds() * speed;
         } else {
             enemy_struct.destination_reached = true;
         }
     }
 }
 
-// Prevents enemies from going outside of the window in the engagement or on fire states
+// Prevents enemies from going outside of the arena in the engagement state.
+// Panicking enemies bounce off the edges.
 pub fn limit_enemy_movement(
-    mut enemies_query: Query<(&mut Transform, &mut Enemy)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut enemies_query: Query<(&mut Transform, &mut Enemy, &StatusEffects)>,
+    arena_bounds: Res<ArenaBounds>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
+    let enemy_bounds = arena_bounds.shrink(PLAYER_CHAINSAW_COLLIDER_SIZE);
 
-    for (mut enemy_transform, mut enemy_struct) in enemies_query.iter_mut() {
+    for (mut enemy_transform, mut enemy_struct, status_effects) in enemies_query.iter_mut() {
         if enemy_struct.state == EnemyState::Spawned {
             continue;
         }
 
-        let min_x = 0.0 + PLAYER_CHAINSAW_COLLIDER_SIZE.x;
-        let max_x = primary_window.width() - PLAYER_CHAINSAW_COLLIDER_SIZE.x;
-        let min_y = 0.0 + PLAYER_CHAINSAW_COLLIDER_SIZE.y;
-        let max_y = primary_window.height() - PLAYER_CHAINSAW_COLLIDER_SIZE.y;
-
-        if enemy_struct.state == EnemyState::OnFire {
-            if enemy_transform.translation.x < min_x || enemy_transform.translation.x > max_x {
+        if status_effects.is_panicking() {
+            if enemy_transform.translation.x < enemy_bounds.min.x
+                || enemy_transform.translation.x > enemy_bounds.max.x
+            {
                 enemy_struct.direction.x *= -1.0;
             }
-            if enemy_transform.translation.y < min_y || enemy_transform.translation.y > max_y {
+            if enemy_transform.translation.y < enemy_bounds.min.y
+                || enemy_transform.translation.y > enemy_bounds.max.y
+            {
                 enemy_struct.direction.y *= -1.0;
             }
         }
 
-        if enemy_transform.translation.x < min_x {
-            enemy_transform.translation.x = min_x;
-        }
-        if enemy_transform.translation.x > max_x {
-            enemy_transform.translation.x = max_x;
-        }
-        if enemy_transform.translation.y < min_y {
-            enemy_transform.translation.y = min_y;
-        }
-        if enemy_transform.translation.y > max_y {
-            enemy_transform.translation.y = max_y;
-        }
+        enemy_transform.translation = enemy_bounds.clamp(enemy_transform.translation);
     }
 }
 
 // Moves all enemies with FollowAI to player, if they have appeared on the arena.
 // If the player is in the chainsaw mode, run from him in the opposite direction.
 pub fn follow_player(
-    mut enemies_query: Query<(&Transform, &mut Enemy), With<FollowAI>>,
+    mut enemies_query: Query<(&mut Enemy, &StatusEffects), With<FollowAI>>,
     player_query: Query<&Transform, With<Player>>,
 ) {
     if let Ok(player_transform) = player_query.get_single() {
-        for (_, mut enemy_struct) in enemies_query.iter_mut() {
-            if enemy_struct.state != EnemyState::Engaging {
+        for (mut enemy_struct, status_effects) in enemies_query.iter_mut() {
+            if enemy_struct.state != EnemyState::Engaging || status_effects.disables_ai() {
                 continue;
             }
 
@@ -322,221 +564,966 @@ pub fn follow_player(
 }
 
 pub fn handle_shooter_ai(
+    mut enemies_query: Query<(
+        &mut Enemy,
+        &mut ShooterAI,
+        &mut BulletEmitter,
+        &StatusEffects,
+    )>,
+    arena_bounds: Res<ArenaBounds>,
+    mut run_rng: ResMut<RunRng>,
+) {
+    for (mut enemy_struct, mut shooter_struct, mut bullet_emitter, status_effects) in
+        enemies_query.iter_mut()
+    {
+        if enemy_struct.state != EnemyState::Engaging || status_effects.disables_ai() {
+            continue;
+        }
+
+        if enemy_struct.destination_reached {
+            let max_offset = arena_bounds.max - arena_bounds.min - enemy_struct.collider.size;
+
+            enemy_struct.destination = (arena_bounds.min
+                + Vec2::new(run_rng.gen::<f32>(), run_rng.gen::<f32>()) * max_offset)
+                .extend(0.0);
+        }
+
+        // shoot projectile
+        if shooter_struct.reload_timer.just_finished() {
+            bullet_emitter.start_volley();
+            shooter_struct.reload_timer.reset();
+        }
+    }
+}
+
+type BossQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static mut Enemy,
+        &'static mut BossAI,
+        &'static mut BulletEmitter,
+        &'static StatusEffects,
+        &'static mut Sprite,
+        &'static mut Handle<Image>,
+    ),
+>;
+
+// Boss goes through BOSS_PHASES as it loses health.
+// Every phase has its own movement pattern and a loop of attacks.
+#[allow(clippy::too_many_arguments)]
+pub fn handle_boss_ai(
     mut commands: Commands,
-    mut enemies_query: Query<(&Transform, &mut Enemy, &mut ShooterAI)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
-    player_query: Query<&Transform, With<Player>>,
+    mut bosses_query: BossQuery,
+    player_query: Query<&Transform, (With<Player>, Without<Enemy>)>,
+    mut game_info: ResMut<GameInfo>,
+    arena_bounds: Res<ArenaBounds>,
     asset_server: Res<AssetServer>,
+    mut run_rng: ResMut<RunRng>,
+    time: Res<Time>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
 
-    if let Ok(player_transform) = player_query.get_single() {
-        for (enemy_transform, mut enemy_struct, mut shooter_struct) in enemies_query.iter_mut() {
-            if enemy_struct.state != EnemyState::Engaging {
-                continue;
+    for (
+        enemy_transform,
+        mut enemy_struct,
+        mut boss_ai,
+        mut bullet_emitter,
+        status_effects,
+        mut enemy_sprite,
+        mut enemy_texture,
+    ) in bosses_query.iter_mut()
+    {
+        if enemy_struct.state != EnemyState::Engaging || status_effects.disables_ai() {
+            continue;
+        }
+
+        // Switch to the latest phase, which threshold has been passed
+        let health_fraction = enemy_struct.current_hp / enemy_struct.max_hp;
+        let phase = BOSS_PHASES
+            .iter()
+            .rposition(|phase| health_fraction <= phase.health_threshold)
+            .unwrap_or(0);
+
+        if phase > boss_ai.phase {
+            let boss_phase = &BOSS_PHASES[phase];
+
+            boss_ai.phase = phase;
+            boss_ai.attack_index = 0;
+            boss_ai.attack_timer = Timer::from_seconds(
+                boss_phase.attack_period * boss_ai.attack_period_multiplier,
+                TimerMode::Repeating,
+            );
+            boss_ai.invulnerability_timer.reset();
+            boss_ai.phase_time = 0.0;
+            boss_ai.is_diving = false;
+
+            *enemy_texture = asset_server.load(boss_phase.sprite);
+            enemy_struct.current_speed = enemy_struct.default_speed * boss_phase.speed_multiplier;
+        }
+
+        let boss_phase = &BOSS_PHASES[boss_ai.phase];
+
+        // Flicker and stay still during the phase transition
+        if boss_ai.is_invulnerable() {
+            boss_ai.invulnerability_timer.tick(time.delta());
+
+            let flicker = (time.elapsed_seconds() * BOSS_PHASE_TRANSITION_FLICKER_SPEED).sin();
+            enemy_sprite.color.set_a(if boss_ai.is_invulnerable() {
+                0.6 + 0.4 * flicker
+            } else {
+                1.0
+            });
+            enemy_struct.destination = enemy_transform.translation;
+            continue;
+        }
+
+        boss_ai.phase_time += time.delta_seconds();
+
+        // === Movement ===
+        let hover_point = arena_bounds.center() + BOSS_HOVER_OFFSET;
+
+        match boss_phase.movement {
+            BossMovement::Hover => {
+                let t = boss_ai.phase_time * BOSS_HOVER_FREQUENCY;
+                enemy_struct.destination = (hover_point
+                    + Vec2::new(t.sin(), (2.0 * t).sin()) * BOSS_HOVER_AMPLITUDE)
+                    .extend(0.0);
+            }
+            BossMovement::Sweep => {
+                if enemy_struct.destination_reached || enemy_struct.destination.y != hover_point.y {
+                    let sweep_bounds = arena_bounds.shrink(enemy_struct.collider.size);
+                    let sweep_x = if enemy_transform.translation.x < hover_point.x {
+                        sweep_bounds.max.x
+                    } else {
+                        sweep_bounds.min.x
+                    };
+
+                    enemy_struct.destination = Vec3::new(sweep_x, hover_point.y, 0.0);
+                }
             }
+            BossMovement::Dive => {
+                if enemy_struct.destination_reached {
+                    enemy_struct.destination = if boss_ai.is_diving {
+                        hover_point.extend(0.0)
+                    } else {
+                        player_transform.translation
+                    };
+                    boss_ai.is_diving = !boss_ai.is_diving;
+                }
+            }
+        }
 
-            let max_x = primary_window.width() - enemy_struct.collider.size.x;
-            let max_y = primary_window.height() - enemy_struct.collider.size.y;
+        // === Attacks ===
+        boss_ai.attack_timer.tick(time.delta());
 
-            if enemy_struct.destination_reached {
-                enemy_struct.destination =
-                    Vec3::new(random::<f32>() * max_x, random::<f32>() * max_y, 0.0);
+        if !boss_ai.attack_timer.just_finished() {
+            continue;
+        }
+
+        let attack = boss_phase.attacks[boss_ai.attack_index];
+        boss_ai.attack_index = (boss_ai.attack_index + 1) % boss_phase.attacks.len();
+
+        let position = enemy_transform.translation;
+
+        match attack {
+            BossAttack::Pattern(pattern_path) => {
+                bullet_emitter.pattern = asset_server.load(pattern_path);
+                bullet_emitter.start_volley();
+            }
+            // Summoned followers still respect the enemies cap
+            BossAttack::SummonFollowers => {
+                let summon_bounds = arena_bounds.shrink(FOLLOWER_COLLIDER_SIZE);
+
+                for _ in 0..BOSS_SUMMON_FOLLOWERS_NUM {
+                    if game_info.enemies_num >= game_info.max_enemies_num {
+                        break;
+                    }
+
+                    let destination = summon_bounds.clamp(
+                        position
+                            + Vec3::new(
+                                run_rng.gen::<f32>() - 0.5,
+                                run_rng.gen::<f32>() - 0.5,
+                                0.0,
+                            ) * enemy_struct.collider.size.extend(0.0)
+                                * 2.0,
+                    );
+
+                    spawn_follower(
+                        &mut commands,
+                        &asset_server,
+                        &mut run_rng,
+                        position,
+                        destination,
+                    );
+                    game_info.enemies_num += 1;
+                }
             }
+        }
+    }
+}
 
-            // shoot projectile
-            if shooter_struct.reload_timer.just_finished() {
-                if enemy_struct.enemy_type == EnemyType::Boss {
-                    commands.spawn((
-                        SpriteBundle {
-                            transform: Transform::from_translation(enemy_transform.translation),
-                            texture: asset_server.load("sprites/projectile.png"),
-                            sprite: Sprite {
-                                custom_size: Some(BOSS_PROJECTILE_SIZE),
-                                ..default()
-                            },
-                            ..default()
-                        },
-                        Projectile {
-                            speed: BOSS_PROJECTILE_SPEED,
-                            direction: (player_transform.translation - enemy_transform.translation)
-                                .normalize(),
-                            collider: Collider {
-                                size: BOSS_PROJECTILE_COLLIDER_SIZE,
-                            },
-                        },
-                    ));
-                } else {
-                    commands.spawn((
-                        SpriteBundle {
-                            transform: Transform::from_translation(enemy_transform.translation),
-                            texture: asset_server.load("sprites/projectile.png"),
-                            sprite: Sprite {
-                                custom_size: Some(SHOOTER_PROJECTILE_SIZE),
-                                ..default()
-                            },
+pub fn load_bullet_patterns(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(BulletPatternHandles {
+        handles: BULLET_PATTERN_PATHS
+            .iter()
+            .map(|path| asset_server.load(*path))
+            .collect(),
+    });
+}
+
+// Fires the shots of the requested volleys following their bullet patterns
+pub fn fire_bullet_emitters(
+    mut commands: Commands,
+    mut emitters_query: Query<(&Transform, &Enemy, &mut BulletEmitter, &StatusEffects)>,
+    player_query: Query<&Transform, With<Player>>,
+    bullet_patterns: Res<Assets<BulletPattern>>,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    let speed_multiplier = settings.difficulty.profile().projectile_speed_multiplier;
+
+    for (enemy_transform, enemy_struct, mut bullet_emitter, status_effects) in
+        emitters_query.iter_mut()
+    {
+        let Some(bullet_pattern) = bullet_patterns.get(&bullet_emitter.pattern) else {
+            continue;
+        };
+
+        bullet_emitter.spin_angle +=
+            bullet_pattern.rotation_speed.to_radians() * time.delta_seconds();
+
+        // Volley is interrupted, when the enemy leaves the engaging state or can't act
+        if enemy_struct.state != EnemyState::Engaging || status_effects.disables_ai() {
+            bullet_emitter.shots_left = 0;
+            continue;
+        }
+
+        // First shot of the volley is fired right away
+        let is_shooting = if bullet_emitter.volley_requested {
+            bullet_emitter.volley_requested = false;
+            bullet_emitter.shots_left = bullet_pattern.bursts;
+            bullet_emitter.shot_timer = Timer::from_seconds(
+                bullet_pattern.shot_delay.max(MIN_SHOT_DELAY),
+                TimerMode::Repeating,
+            );
+            true
+        } else if bullet_emitter.shots_left > 0 {
+            bullet_emitter.shot_timer.tick(time.delta());
+            bullet_emitter.shot_timer.just_finished()
+        } else {
+            false
+        };
+
+        if !is_shooting || bullet_emitter.shots_left == 0 {
+            continue;
+        }
+
+        bullet_emitter.shots_left -= 1;
+
+        let to_player = player_transform.translation - enemy_transform.translation;
+        let base_angle = match bullet_pattern.aim {
+            AimMode::Player => to_player.y.atan2(to_player.x),
+            AimMode::Fixed(angle) => angle.to_radians(),
+            AimMode::Spinning => bullet_emitter.spin_angle,
+        };
+
+        for angle in bullet_pattern.shot_angles(base_angle) {
+            let direction = Vec2::from_angle(angle).extend(0.0);
+
+            if let Some(laser_beam) = bullet_pattern.laser {
+                // Drawn under the enemies, sprite is resized by handle_lasers
+                commands.spawn((
+                    SpriteBundle {
+                        transform: Transform::from_translation(
+                            enemy_transform.translation.truncate().extend(-0.5),
+                        )
+                        .with_rotation(Quat::from_rotation_z(angle)),
+                        sprite: Sprite {
+                            anchor: Anchor::CenterLeft,
+                            custom_size: Some(Vec2::ZERO),
                             ..default()
                         },
-                        Projectile {
-                            speed: SHOOTER_PROJECTILE_SPEED,
-                            direction: (player_transform.translation - enemy_transform.translation)
-                                .normalize(),
-                            collider: Collider {
-                                size: SHOOTER_PROJECTILE_COLLIDER_SIZE,
-                            },
-                        },
-                    ));
+                        ..default()
+                    },
+                    Laser {
+                        direction,
+                        length: laser_beam.length,
+                        width: laser_beam.width,
+                        charge_timer: Timer::from_seconds(
+                            laser_beam.charge_duration,
+                            TimerMode::Once,
+                        ),
+                        fire_timer: Timer::from_seconds(laser_beam.fire_duration, TimerMode::Once),
+                    },
+                ));
+                continue;
+            }
+
+            let mut projectile = commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_translation(enemy_transform.translation),
+                    texture: asset_server.load("sprites/projectile.png"),
+                    sprite: Sprite {
+                        custom_size: Some(Vec2::splat(bullet_pattern.projectile_size)),
+                        ..default()
+                    },
+                    ..default()
+                },
+                Projectile {
+                    speed: bullet_pattern.speed.start * speed_multiplier,
+                    direction,
+                    collider: Collider {
+                        size: Vec2::splat(bullet_pattern.collider_size),
+                    },
+                    faction: Faction::Enemy,
+                    reflect_damage: bullet_pattern.reflect_damage,
+                    lifetime: Timer::from_seconds(bullet_pattern.lifetime, TimerMode::Once),
+                },
+            ));
+
+            if let Some(turn_rate) = bullet_pattern.homing {
+                projectile.insert(Homing {
+                    turn_rate: turn_rate.to_radians(),
+                });
+            }
+
+            if bullet_pattern.bounces > 0 {
+                projectile.insert(Bouncing {
+                    bounces_left: bullet_pattern.bounces,
+                });
+            }
+
+            if let Some(sine_wave) = bullet_pattern.sine {
+                projectile.insert(SineMotion {
+                    amplitude: sine_wave.amplitude,
+                    frequency: sine_wave.frequency,
+                    elapsed: 0.0,
+                    offset: 0.0,
+                });
+            }
+
+            if let Some(end_speed) = bullet_pattern
+                .speed
+                .end
+                .filter(|_| bullet_pattern.speed.duration > 0.0)
+            {
+                projectile.insert(ProjectileSpeedCurve {
+                    start: bullet_pattern.speed.start * speed_multiplier,
+                    end: end_speed * speed_multiplier,
+                    timer: Timer::from_seconds(bullet_pattern.speed.duration, TimerMode::Once),
+                });
+            }
+        }
+    }
+}
+
+type ChargerLinesQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Transform, &'static mut Visibility),
+    (With<ChargerWindUpLine>, Without<Enemy>),
+>;
+type ChargerPlayerQuery<'w, 's> =
+    Query<'w, 's, &'static Transform, (With<Player>, Without<Enemy>, Without<ChargerWindUpLine>)>;
+
+// Charger stalks the player, stops to telegraph the attack and dashes in a straight line.
+// Crashing into the arena edge stuns it. Being disabled (e.g. on fire) interrupts the attack.
+pub fn handle_charger_ai(
+    mut chargers_query: Query<(
+        &mut Transform,
+        &mut Enemy,
+        &mut ChargerAI,
+        &mut StatusEffects,
+        &Children,
+    )>,
+    mut lines_query: ChargerLinesQuery,
+    player_query: ChargerPlayerQuery,
+    arena_bounds: Res<ArenaBounds>,
+    time: Res<Time>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    let enemy_bounds = arena_bounds.shrink(PLAYER_CHAINSAW_COLLIDER_SIZE);
+
+    for (mut enemy_transform, mut enemy_struct, mut charger_ai, mut status_effects, children) in
+        chargers_query.iter_mut()
+    {
+        let line_entity = children
+            .iter()
+            .find(|&&child| lines_query.contains(child))
+            .copied();
+
+        if enemy_struct.state != EnemyState::Engaging || status_effects.disables_ai() {
+            if matches!(
+                charger_ai.state,
+                ChargerState::WindingUp | ChargerState::Dashing
+            ) {
+                charger_ai.state = ChargerState::Recovering;
+                charger_ai.state_timer =
+                    Timer::from_seconds(CHARGER_RECOVERY_DURATION, TimerMode::Once);
+            }
+            if let Some((_, mut visibility)) =
+                line_entity.and_then(|line_entity| lines_query.get_mut(line_entity).ok())
+            {
+                *visibility = Visibility::Hidden;
+            }
+            continue;
+        }
+
+        charger_ai.state_timer.tick(time.delta());
+
+        let to_player = player_transform.translation - enemy_transform.translation;
+
+        match charger_ai.state {
+            ChargerState::Stalking => {
+                enemy_struct.destination = player_transform.translation;
+
+                if to_player.length() < CHARGER_ATTACK_DISTANCE {
+                    charger_ai.state = ChargerState::WindingUp;
+                    charger_ai.state_timer =
+                        Timer::from_seconds(CHARGER_WIND_UP_DURATION, TimerMode::Once);
+                }
+            }
+            ChargerState::WindingUp => {
+                enemy_struct.destination = enemy_transform.translation;
+
+                // Keep aiming until the dash starts
+                if let Some(direction) = to_player.try_normalize() {
+                    charger_ai.dash_direction = direction;
+                    enemy_struct.direction = direction;
+                }
+
+                if charger_ai.state_timer.finished() {
+                    charger_ai.state = ChargerState::Dashing;
+                    charger_ai.state_timer =
+                        Timer::from_seconds(CHARGER_DASH_DURATION, TimerMode::Once);
                 }
-                shooter_struct.reload_timer.reset();
             }
+            ChargerState::Dashing => {
+                // Dash speed is based on the current speed, so the chainsaw slow down still applies
+                let dash_speed =
+                    enemy_struct.current_speed.max(0.0) * CHARGER_DASH_SPEED_MULTIPLIER;
+                let next_position = enemy_transform.translation
+                    + charger_ai.dash_direction * dash_speed * time.delta_seconds();
+
+                enemy_transform.translation = enemy_bounds.clamp(next_position);
+                enemy_struct.destination = enemy_transform.translation;
+
+                if !enemy_bounds.contains(next_position) {
+                    status_effects.apply(StatusEffectKind::Stunned);
+                    charger_ai.state = ChargerState::Recovering;
+                    charger_ai.state_timer =
+                        Timer::from_seconds(CHARGER_RECOVERY_DURATION, TimerMode::Once);
+                } else if charger_ai.state_timer.finished() {
+                    charger_ai.state = ChargerState::Recovering;
+                    charger_ai.state_timer =
+                        Timer::from_seconds(CHARGER_RECOVERY_DURATION, TimerMode::Once);
+                }
+            }
+            ChargerState::Recovering => {
+                enemy_struct.destination = player_transform.translation;
+
+                if charger_ai.state_timer.finished() {
+                    charger_ai.state = ChargerState::Stalking;
+                }
+            }
+        }
+
+        if let Some((mut line_transform, mut visibility)) =
+            line_entity.and_then(|line_entity| lines_query.get_mut(line_entity).ok())
+        {
+            if charger_ai.state == ChargerState::WindingUp {
+                *visibility = Visibility::Inherited;
+                line_transform.rotation = Quat::from_rotation_z(
+                    charger_ai
+                        .dash_direction
+                        .y
+                        .atan2(charger_ai.dash_direction.x),
+                );
+            } else {
+                *visibility = Visibility::Hidden;
+            }
+        }
+    }
+}
+
+type ShieldedPlayerQuery<'w, 's> =
+    Query<'w, 's, &'static Transform, (With<Player>, Without<Shielded>, Without<Shield>)>;
+
+// Shielded enemies slowly turn to face the player, the shield sprite follows the facing
+pub fn turn_shielded_enemies(
+    mut enemies_query: Query<(&Transform, &mut Shielded, &StatusEffects, &Children)>,
+    mut shields_query: Query<&mut Transform, (With<Shield>, Without<Shielded>)>,
+    player_query: ShieldedPlayerQuery,
+    time: Res<Time>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for (enemy_transform, mut shielded, status_effects, children) in enemies_query.iter_mut() {
+        // Disabled enemy can't turn the shield, so it's a chance to get behind it
+        if status_effects.disables_ai() {
+            continue;
+        }
+
+        if let Some(to_player) =
+            (player_transform.translation - enemy_transform.translation).try_normalize()
+        {
+            let facing_angle = shielded.facing.y.atan2(shielded.facing.x);
+            let angle_difference =
+                Vec2::from_angle(facing_angle).angle_between(to_player.truncate());
+            let max_turn = SHIELDED_TURN_SPEED * time.delta_seconds();
+            let new_angle = facing_angle + angle_difference.clamp(-max_turn, max_turn);
+
+            shielded.facing = Vec2::from_angle(new_angle).extend(0.0);
+        }
+
+        for &child in children.iter() {
+            if let Ok(mut shield_transform) = shields_query.get_mut(child) {
+                shield_transform.translation = shielded.facing * SHIELDED_SHIELD_OFFSET;
+                shield_transform.rotation =
+                    Quat::from_rotation_z(shielded.facing.y.atan2(shielded.facing.x));
+            }
+        }
+    }
+}
+
+// Reflected projectiles hurt the enemies, but shields still stop them
+pub fn check_reflected_projectile_collision(
+    mut commands: Commands,
+    mut enemy_take_damage_event_writer: EventWriter<EnemyTakeDamageEvent>,
+    projectiles_query: Query<(Entity, &Transform, &Projectile)>,
+    enemies_query: Query<(Entity, &Transform, &Enemy, Option<&Shielded>)>,
+) {
+    for (projectile_entity, projectile_transform, projectile_struct) in projectiles_query.iter() {
+        if projectile_struct.faction != Faction::Player {
+            continue;
+        }
+
+        for (enemy_entity, enemy_transform, enemy_struct, shielded) in enemies_query.iter() {
+            if collide(
+                projectile_transform.translation,
+                projectile_struct.collider.size,
+                enemy_transform.translation,
+                enemy_struct.collider.size,
+            )
+            .is_none()
+            {
+                continue;
+            }
+
+            let direction_to_projectile = (projectile_transform.translation
+                - enemy_transform.translation)
+                .normalize_or_zero();
+
+            if enemy_struct.enemy_type != EnemyType::Boss {
+                commands.entity(enemy_entity).insert(EnemyKnockback {
+                    velocity: projectile_struct.direction * REFLECTED_PROJECTILE_KNOCKBACK_SPEED,
+                });
+            }
+
+            if let Some(damage_multiplier) = shielded.map_or(Some(1.0), |shielded| {
+                shielded.hit_multiplier(direction_to_projectile)
+            }) {
+                enemy_take_damage_event_writer.send(EnemyTakeDamageEvent {
+                    enemy_entity,
+                    damage: projectile_struct.reflect_damage * damage_multiplier,
+                    source: DamageSource::ReflectedProjectile,
+                    status_effect: Some(StatusEffectKind::Slowed),
+                    is_critical: damage_multiplier > 1.0,
+                });
+            }
+
+            commands.entity(projectile_entity).despawn();
+            break;
         }
     }
 }
 
+type DamagedEnemiesQuery<'w, 's> = Query<
+    'w,
+    's,
+    (
+        &'static Transform,
+        &'static mut Enemy,
+        &'static mut StatusEffects,
+        Option<&'static Splitter>,
+        Option<&'static BossAI>,
+    ),
+>;
+
+#[allow(clippy::too_many_arguments)]
 pub fn handle_enemy_take_damage_event(
     mut commands: Commands,
     mut enemy_take_damage_event_reader: EventReader<EnemyTakeDamageEvent>,
-    mut enemies_query: Query<&mut Enemy>,
+    mut damage_number_event_writer: EventWriter<DamageNumberEvent>,
+    mut combo_hit_event_writer: EventWriter<ComboHitEvent>,
+    mut enemy_killed_event_writer: EventWriter<EnemyKilledEvent>,
+    mut enemies_query: DamagedEnemiesQuery,
     mut game_info: ResMut<GameInfo>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut boss_encounters: ResMut<BossEncounters>,
+    combo: Res<Combo>,
+    arena_bounds: Res<ArenaBounds>,
+    settings: Res<GameSettings>,
+    mut run_rng: ResMut<RunRng>,
     time: Res<Time>,
     asset_server: Res<AssetServer>,
 ) {
     for enemy_damage_event in enemy_take_damage_event_reader.iter() {
         // Check if Enemy component exists on the entity from EnemyTakeDamageEvent
         // (it should definitely exists, but better to check twice)
-        if let Ok(mut enemy_struct) = enemies_query.get_mut(enemy_damage_event.enemy_entity) {
+        if let Ok((enemy_transform, mut enemy_struct, mut status_effects, splitter, boss_ai)) =
+            enemies_query.get_mut(enemy_damage_event.enemy_entity)
+        {
+            // Boss can't be hurt while changing the phase
+            if boss_ai.is_some_and(|boss_ai| boss_ai.is_invulnerable()) {
+                continue;
+            }
+
+            // Enemy is already dead and waits for the despawn
+            if enemy_struct.current_hp <= 0.0 {
+                continue;
+            }
+
+            // Drain enemy's hp, the chainsaw also slows it down
+            let damage = enemy_damage_event.damage * status_effects.damage_taken_multiplier();
+            enemy_struct.current_hp -= damage;
+
+            damage_number_event_writer.send(DamageNumberEvent {
+                target: Some(enemy_damage_event.enemy_entity),
+                position: enemy_transform.translation,
+                amount: damage,
+                kind: match enemy_damage_event.source {
+                    DamageSource::StatusEffect(StatusEffectKind::Burning) => DamageNumberKind::Burn,
+                    DamageSource::StatusEffect(StatusEffectKind::Bleeding) => {
+                        DamageNumberKind::Bleed
+                    }
+                    _ if enemy_damage_event.is_critical => DamageNumberKind::Critical,
+                    _ => DamageNumberKind::Normal,
+                },
+            });
+
+            if let Some(status_effect) = enemy_damage_event.status_effect {
+                status_effects.apply(status_effect);
+            }
+
+            if enemy_damage_event.source == DamageSource::Chainsaw {
+                enemy_struct.current_speed -=
+                    time.delta_seconds() * CHAINSAW_ENEMY_SLOW_DOWN_FACTOR;
+            }
+
             if enemy_struct.current_hp <= 0.0 {
+                if enemy_damage_event.source == DamageSource::ReflectedProjectile {
+                    player_info.reflect_kills += 1;
+                }
+
+                let combo_multiplier = combo.multiplier();
+                // Burn deaths are the player's doing, since the player started the fire
+                if enemy_damage_event.source
+                    == DamageSource::StatusEffect(StatusEffectKind::Burning)
+                {
+                    player_info.blood += (enemy_struct.max_hp * combo_multiplier).round() as usize;
+                }
+                player_info.score +=
+                    (enemy_struct.max_hp * SCORE_PER_ENEMY_HP * combo_multiplier).round() as usize;
+                combo_hit_event_writer.send(ComboHitEvent);
+                enemy_killed_event_writer.send(EnemyKilledEvent {
+                    enemy_type: enemy_struct.enemy_type,
+                    source: enemy_damage_event.source,
+                });
+
                 let enemies_num = game_info.enemies_num;
                 game_info.enemies_num = if enemies_num != 0 { enemies_num - 1 } else { 0 };
 
-                if enemy_struct.enemy_type == EnemyType::Boss {
-                    let primary_window = window_query.get_single().unwrap();
+                // Children are counted in enemies_num, but are spawned even above the max,
+                // otherwise killing a splitter in a full arena would be a free kill
+                if let Some(splitter) = splitter {
+                    if splitter.generation < SPLITTER_MAX_GENERATION {
+                        let child_bounds = arena_bounds.shrink(PLAYER_CHAINSAW_COLLIDER_SIZE);
+                        let angle_offset = run_rng.gen::<f32>() * std::f32::consts::TAU;
+
+                        for i in 0..SPLITTER_CHILDREN_NUM {
+                            let angle = angle_offset
+                                + i as f32 * std::f32::consts::TAU / SPLITTER_CHILDREN_NUM as f32;
+                            let destination = child_bounds.clamp(
+                                enemy_transform.translation
+                                    + Vec3::new(angle.cos(), angle.sin(), 0.0)
+                                        * SPLITTER_SCATTER_DISTANCE,
+                            );
+
+                            spawn_splitter(
+                                &mut commands,
+                                &asset_server,
+                                &mut run_rng,
+                                enemy_transform.translation,
+                                destination,
+                                splitter.generation + 1,
+                            );
+                        }
+
+                        game_info.enemies_num += SPLITTER_CHILDREN_NUM;
+                    }
+                }
 
+                if enemy_struct.enemy_type == EnemyType::Boss
+                    && settings.game_mode == GameMode::Endless
+                {
+                    // Regular spawns go on until the next boss
+                    game_info.is_boss_spawned = false;
+                    boss_encounters.killed += 1;
+                    boss_encounters.next_depth = game_info.player_progress + ENDLESS_BOSS_INTERVAL;
+                } else if enemy_struct.enemy_type == EnemyType::Boss {
                     println!("Spawned end screen");
 
                     commands.spawn(SpriteBundle {
-                        transform: Transform::from_xyz(
-                            primary_window.width() / 2.,
-                            primary_window.height() / 2.,
-                            0.0,
-                        ),
+                        transform: Transform::from_translation(arena_bounds.center().extend(0.0)),
                         texture: asset_server.load("sprites/end_screen.png"),
                         sprite: Sprite {
-                            custom_size: Some(Vec2::new(260. * 2., 320. * 2.)),
+                            custom_size: Some(ARENA_SIZE),
                             ..default()
                         },
                         ..default()
                     });
                 }
 
-                commands.entity(enemy_damage_event.enemy_entity).despawn();
-            } else {
-                // Drain enemy's hp and slow it down
-                enemy_struct.current_hp -=
-                    PLAYER_DAMAGE as f32 * PLAYER_DAMAGE_SPEED * time.delta_seconds();
-                enemy_struct.current_speed -=
-                    time.delta_seconds() * CHAINSAW_ENEMY_SLOW_DOWN_FACTOR;
+                commands
+                    .entity(enemy_damage_event.enemy_entity)
+                    .despawn_recursive();
             }
         };
     }
 }
 
-// Enemy becomes ignited and changes his AI
+// Engaging enemies catch fire and panic
+// Fire wave ignites the enemies and blasts them away from the player
 pub fn handle_fire_wave_event(
+    mut commands: Commands,
     mut fire_wave_event_reader: EventReader<ChainsawFireWave>,
-    mut enemies_query: Query<(&mut Enemy, &mut FireTimer)>,
+    mut combo_hit_event_writer: EventWriter<ComboHitEvent>,
+    mut enemies_query: Query<(Entity, &Transform, &Enemy, &mut StatusEffects)>,
+    player_query: Query<&Transform, With<Player>>,
 ) {
-    for _ in fire_wave_event_reader.iter() {
-        for (mut enemy_struct, mut enemy_fire_timer) in enemies_query.iter_mut() {
-            if enemy_struct.state != EnemyState::Engaging {
-                continue;
-            }
+    if fire_wave_event_reader.iter().count() == 0 {
+        return;
+    }
+
+    let player_translation = player_query
+        .get_single()
+        .map_or(Vec3::ZERO, |player_transform| player_transform.translation);
 
-            enemy_struct.state = EnemyState::OnFire;
-            enemy_struct.current_speed += ENEMY_ON_FIRE_SPEED_GAIN;
-            enemy_struct.is_green_decreasing = true;
-            enemy_fire_timer.timer.reset();
+    for (enemy_entity, enemy_transform, enemy_struct, mut status_effects) in
+        enemies_query.iter_mut()
+    {
+        if enemy_struct.state != EnemyState::Engaging {
+            continue;
         }
 
-        return;
+        if enemy_struct.enemy_type != EnemyType::Boss {
+            let direction = (enemy_transform.translation - player_translation).normalize_or_zero();
+            commands.entity(enemy_entity).insert(EnemyKnockback {
+                velocity: direction * FIRE_WAVE_KNOCKBACK_SPEED,
+            });
+        }
+
+        if !status_effects.has(StatusEffectKind::Burning) {
+            combo_hit_event_writer.send(ComboHitEvent);
+        }
+        status_effects.apply(StatusEffectKind::Burning);
     }
 }
 
-pub fn handle_on_fire_state(
-    mut enemies_query: Query<(&mut Sprite, &mut Enemy, &FireTimer)>,
+pub fn apply_enemy_knockback(
+    mut commands: Commands,
+    mut enemies_query: Query<(Entity, &mut Transform, &mut EnemyKnockback)>,
     time: Res<Time>,
 ) {
-    for (mut enemy_sprite, mut enemy_struct, enemy_fire_timer) in enemies_query.iter_mut() {
-        if enemy_struct.state != EnemyState::OnFire {
-            continue;
+    for (enemy_entity, mut enemy_transform, mut enemy_knockback) in enemies_query.iter_mut() {
+        enemy_transform.translation += enemy_knockback.velocity * time.delta_seconds();
+        enemy_knockback.velocity *= (1.0 - ENEMY_KNOCKBACK_DAMPING * time.delta_seconds()).max(0.0);
+
+        if enemy_knockback.velocity.length() < ENEMY_KNOCKBACK_MIN_SPEED {
+            commands.entity(enemy_entity).remove::<EnemyKnockback>();
         }
+    }
+}
 
-        if enemy_fire_timer.timer.just_finished() {
-            enemy_struct.state = EnemyState::Engaging;
-            enemy_struct.current_speed -= ENEMY_ON_FIRE_SPEED_GAIN;
-            enemy_sprite.color = Color::WHITE;
-            enemy_struct.is_green_decreasing = false;
-            enemy_struct.destination_reached = false;
+pub fn tick_enemy_spawn_timer(time: Res<Time>, mut enemy_spawn_timer: ResMut<EnemySpawnTimer>) {
+    enemy_spawn_timer.timer.tick(time.delta());
+}
 
-            println!("Enemy has extinguished the fire");
+pub fn tick_shooter_reloading_timer(
+    mut enemy_queries: Query<(&mut ShooterAI, &Enemy, &StatusEffects)>,
+    time: Res<Time>,
+) {
+    for (mut shooter_struct, enemy_struct, status_effects) in enemy_queries.iter_mut() {
+        if enemy_struct.state != EnemyState::Engaging || status_effects.disables_ai() {
             continue;
         }
 
-        // Periodical orange flash
-        let mut enemy_color = enemy_sprite.color;
+        shooter_struct.reload_timer.tick(time.delta());
+    }
+}
 
-        if enemy_color.g() * 255.0 >= FIRE_FLASH_GREEN_MAX {
-            enemy_struct.is_green_decreasing = true;
-        } else if enemy_color.g() * 255.0 <= FIRE_FLASH_GREEN_MIN {
-            enemy_struct.is_green_decreasing = false;
-        }
+fn enemy_spawn_period(difficulty_profile: &DifficultyProfile, depth: f32) -> Duration {
+    Duration::from_secs_f32(
+        ENEMY_SPAWN_PERIOD * difficulty_profile.spawn_period_multiplier
+            / endless_spawn_rate_multiplier(depth),
+    )
+}
 
-        if enemy_struct.is_green_decreasing {
-            enemy_color = Color::rgb_linear(
-                enemy_color.r(),
-                enemy_color.g() - FIRE_COLOR_SPEED * time.delta_seconds(),
-                enemy_color.b() - FIRE_COLOR_SPEED * time.delta_seconds(),
-            );
+// Spawn rate follows the chosen difficulty
+pub fn apply_difficulty_to_enemy_spawn_timer(
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+    game_info: Res<GameInfo>,
+    settings: Res<GameSettings>,
+) {
+    let spawn_period = enemy_spawn_period(settings.difficulty.profile(), game_info.player_progress);
 
-            println!("Enemy's color is decreasing");
-        } else {
-            enemy_color = Color::rgb_linear(
-                enemy_color.r(),
-                enemy_color.g() + FIRE_COLOR_SPEED * time.delta_seconds(),
-                enemy_color.b() + FIRE_COLOR_SPEED * time.delta_seconds(),
-            );
+    enemy_spawn_timer.timer.set_duration(spawn_period);
+    enemy_spawn_timer.timer.reset();
+}
 
-            println!("Enemy's color is increasing");
-        }
+// Enemies spawn faster and faster past MAX_DEPTH in the endless mode
+pub fn ramp_enemy_spawn_timer(
+    mut enemy_spawn_timer: ResMut<EnemySpawnTimer>,
+    game_info: Res<GameInfo>,
+    settings: Res<GameSettings>,
+) {
+    let spawn_period = enemy_spawn_period(settings.difficulty.profile(), game_info.player_progress);
 
-        enemy_sprite.color = enemy_color;
+    if enemy_spawn_timer.timer.duration() != spawn_period {
+        enemy_spawn_timer.timer.set_duration(spawn_period);
     }
 }
 
-pub fn tick_enemy_spawn_timer(time: Res<Time>, mut enemy_spawn_timer: ResMut<EnemySpawnTimer>) {
-    enemy_spawn_timer.timer.tick(time.delta());
+// Every spawned enemy (summoned and split ones too) gets the difficulty's health and speed,
+// ramped up by the depth in the endless mode
+pub fn apply_difficulty_to_new_enemies(
+    mut enemies_query: Query<&mut Enemy, Added<Enemy>>,
+    game_info: Res<GameInfo>,
+    settings: Res<GameSettings>,
+) {
+    let difficulty_profile = settings.difficulty.profile();
+    let health_multiplier =
+        difficulty_profile.enemy_health_multiplier * endless_ramp(game_info.player_progress);
+    let speed_multiplier = difficulty_profile.enemy_speed_multiplier
+        * endless_speed_multiplier(game_info.player_progress);
+
+    for mut enemy_struct in enemies_query.iter_mut() {
+        enemy_struct.max_hp *= health_multiplier;
+        enemy_struct.current_hp *= health_multiplier;
+        enemy_struct.default_speed *= speed_multiplier;
+        enemy_struct.current_speed *= speed_multiplier;
+    }
 }
 
-// Tick the timer only when the enemy is on fire
-pub fn tick_enemy_fire_timer(mut enemy_queries: Query<(&mut FireTimer, &Enemy)>, time: Res<Time>) {
-    for (mut enemy_fire_timer, enemy_struct) in enemy_queries.iter_mut() {
-        if enemy_struct.state == EnemyState::OnFire {
-            enemy_fire_timer.timer.tick(time.delta());
-        }
+type NewEnemiesQuery<'w, 's> =
+    Query<'w, 's, (Entity, &'static Enemy), (Added<Enemy>, Without<BossAI>)>;
+
+// Adds the hidden health bar over every new enemy, except the boss
+pub fn attach_enemy_health_bars(mut commands: Commands, enemies_query: NewEnemiesQuery) {
+    for (enemy_entity, enemy_struct) in enemies_query.iter() {
+        let bar_height = enemy_struct.collider.size.y / 2.0 + ENEMY_HEALTH_BAR_MARGIN;
+
+        commands.entity(enemy_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    SpriteBundle {
+                        // In front of the enemy
+                        transform: Transform::from_xyz(0.0, bar_height, 1.0),
+                        sprite: Sprite {
+                            color: ENEMY_HEALTH_BAR_BACKGROUND_COLOR,
+                            custom_size: Some(ENEMY_HEALTH_BAR_SIZE),
+                            ..default()
+                        },
+                        visibility: Visibility::Hidden,
+                        ..default()
+                    },
+                    EnemyHealthBar::new(enemy_struct.current_hp),
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        SpriteBundle {
+                            // Grows from the left edge of the background
+                            transform: Transform::from_xyz(
+                                -ENEMY_HEALTH_BAR_SIZE.x / 2.0,
+                                0.0,
+                                0.1,
+                            ),
+                            sprite: Sprite {
+                                color: ENEMY_HEALTH_BAR_FILL_COLOR,
+                                custom_size: Some(ENEMY_HEALTH_BAR_SIZE),
+                                anchor: Anchor::CenterLeft,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        EnemyHealthBarFill,
+                    ));
+                });
+        });
     }
 }
 
-pub fn tick_shooter_reloading_timer(
-    mut enemy_queries: Query<(&mut ShooterAI, &Enemy)>,
+type HealthBarFillsQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Transform, &'static mut Sprite),
+    (With<EnemyHealthBarFill>, Without<EnemyHealthBar>),
+>;
+
+// Shows the bar, when the enemy loses health, and fades it out after a while
+pub fn update_enemy_health_bars(
+    enemies_query: Query<(&Enemy, &Children)>,
+    mut bars_query: Query<(&mut EnemyHealthBar, &mut Sprite, &mut Visibility, &Children)>,
+    mut fills_query: HealthBarFillsQuery,
     time: Res<Time>,
 ) {
-    for (mut shooter_struct, enemy_struct) in enemy_queries.iter_mut() {
-        if enemy_struct.state != EnemyState::Engaging {
-            continue;
-        }
+    for (enemy_struct, children) in enemies_query.iter() {
+        for &child in children.iter() {
+            let Ok((mut health_bar, mut bar_sprite, mut bar_visibility, bar_children)) =
+                bars_query.get_mut(child)
+            else {
+                continue;
+            };
 
-        shooter_struct.reload_timer.tick(time.delta());
+            if enemy_struct.current_hp < health_bar.shown_hp {
+                health_bar.visible_timer.reset();
+            }
+            health_bar.shown_hp = enemy_struct.current_hp;
+            health_bar.visible_timer.tick(time.delta());
+
+            let alpha = health_bar.alpha();
+            *bar_visibility = if alpha > 0.0 {
+                Visibility::Inherited
+            } else {
+                Visibility::Hidden
+            };
+            bar_sprite.color.set_a(alpha);
+
+            let health_fraction = (enemy_struct.current_hp / enemy_struct.max_hp).clamp(0.0, 1.0);
+
+            for &bar_child in bar_children.iter() {
+                if let Ok((mut fill_transform, mut fill_sprite)) = fills_query.get_mut(bar_child) {
+                    fill_transform.scale.x = health_fraction;
+                    fill_sprite.color.set_a(alpha);
+                }
+            }
+        }
     }
 }
 
@@ -1,6 +1,26 @@
+use super::damage_numbers::DamageNumberKind;
+use super::enemy::EnemyType;
+use super::status_effects::StatusEffectKind;
+
 use bevy::prelude::*;
+use serde::Serialize;
 
-pub struct PlayerTakeDamageEvent;
+pub struct PlayerTakeDamageEvent {
+    pub source: PlayerDamageSource,
+}
+
+// Sent for the hits, which actually took the health. Shield absorbed hits don't count.
+pub struct PlayerLostHealthEvent {
+    pub source: PlayerDamageSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub enum PlayerDamageSource {
+    EnemyContact,
+    Projectile,
+    Laser,
+    Hazard,
+}
 
 pub struct PlayerTransitionToRegularFormEvent;
 
@@ -8,6 +28,49 @@ pub struct GameOverEvent;
 
 pub struct EnemyTakeDamageEvent {
     pub enemy_entity: Entity,
+    pub damage: f32,
+    pub source: DamageSource,
+    // Effect applied to the enemy along with the damage
+    pub status_effect: Option<StatusEffectKind>,
+    // Hit from behind the shield
+    pub is_critical: bool,
+}
+
+// Damage, which has finished the enemy
+pub struct EnemyKilledEvent {
+    pub enemy_type: EnemyType,
+    pub source: DamageSource,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum DamageSource {
+    Chainsaw,
+    ReflectedProjectile,
+    StatusEffect(StatusEffectKind),
+    // Enemy has been knocked into a hazard
+    Hazard,
+}
+
+// Chainsaw has hit the shield. Direction points from the enemy to the player.
+pub struct ChainsawDeflectedEvent {
+    pub direction: Vec3,
+}
+
+// Chainsaw is cutting through a hazard, which heats it up
+pub struct ChainsawCutHazardEvent {
+    pub heat_multiplier: f32,
 }
 
 pub struct ChainsawFireWave;
+
+// Kill, reflected projectile or ignition, which keeps the combo going
+pub struct ComboHitEvent;
+
+// Shows the floating number over the target
+pub struct DamageNumberEvent {
+    // Number follows the target, while it exists
+    pub target: Option<Entity>,
+    pub position: Vec3,
+    pub amount: f32,
+    pub kind: DamageNumberKind,
+}
@@ -0,0 +1,12 @@
+use super::HazardKind;
+use crate::game::components::Collider;
+
+use bevy::prelude::*;
+
+#[derive(Component)]
+pub struct Hazard {
+    pub kind: HazardKind,
+    pub collider: Collider,
+    // Left to cut with the chainsaw, None for the hazards, which can't be cut
+    pub health: Option<f32>,
+}
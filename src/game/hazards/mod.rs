@@ -0,0 +1,95 @@
+pub mod components;
+mod resources;
+mod systems;
+
+use super::status_effects::StatusEffectKind;
+use super::GameState;
+use resources::*;
+use systems::*;
+
+use std::ops::Range;
+
+use bevy::prelude::*;
+
+// Hazards scroll up with the walls, same as the pickups
+const HAZARD_SPEED: f32 = 100.0;
+const HAZARD_SPAWN_PERIOD: f32 = 4.0;
+// Enemies knocked into a hazard take this damage once
+const HAZARD_IMPACT_DAMAGE: f32 = 10.0;
+
+// === Rock outcrop ===
+const ROCK_OUTCROP_LENGTH: Range<f32> = 90.0..170.0;
+const ROCK_OUTCROP_THICKNESS: f32 = 36.0;
+const ROCK_OUTCROP_COLOR: Color = Color::rgb(0.4, 0.35, 0.3);
+
+// === Spinning blade ===
+const SPINNING_BLADE_SIZE: Vec2 = Vec2::new(34.0, 34.0);
+const SPINNING_BLADE_HEALTH: f32 = 8.0;
+// Radians per second
+const SPINNING_BLADE_ROTATION_SPEED: f32 = 12.0;
+const SPINNING_BLADE_COLOR: Color = Color::rgb(0.8, 0.82, 0.85);
+
+// === Electrified cable ===
+// Fraction of the arena width
+const ELECTRIFIED_CABLE_LENGTH: Range<f32> = 0.4..0.7;
+const ELECTRIFIED_CABLE_THICKNESS: f32 = 6.0;
+const ELECTRIFIED_CABLE_HEALTH: f32 = 4.0;
+const ELECTRIFIED_CABLE_FLICKER_SPEED: f32 = 30.0;
+const ELECTRIFIED_CABLE_COLOR: Color = Color::rgb(0.45, 0.8, 1.0);
+
+pub struct HazardsPlugin;
+
+impl Plugin for HazardsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<HazardSpawnTimer>().add_systems(
+            (
+                tick_hazard_spawn_timer,
+                spawn_hazards_over_time.after(tick_hazard_spawn_timer),
+                move_hazards_vertically,
+                animate_hazards,
+                despawn_hazards,
+                check_player_hazard_collision,
+                check_knocked_enemy_hazard_collision,
+            )
+                .in_set(OnUpdate(GameState::Running)),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HazardKind {
+    // Juts out of a side wall, deflects the chainsaw
+    RockOutcrop,
+    SpinningBlade,
+    // Stretched from a side wall
+    ElectrifiedCable,
+}
+
+impl HazardKind {
+    // None, when the chainsaw can't cut the hazard
+    pub fn health(&self) -> Option<f32> {
+        match self {
+            HazardKind::RockOutcrop => None,
+            HazardKind::SpinningBlade => Some(SPINNING_BLADE_HEALTH),
+            HazardKind::ElectrifiedCable => Some(ELECTRIFIED_CABLE_HEALTH),
+        }
+    }
+
+    // Cutting the hazard heats the chainsaw this much faster than cutting an enemy
+    pub fn cut_heat_multiplier(&self) -> f32 {
+        match self {
+            HazardKind::RockOutcrop => 0.0,
+            HazardKind::SpinningBlade => 2.0,
+            HazardKind::ElectrifiedCable => 4.0,
+        }
+    }
+
+    // Applied to the enemies knocked into the hazard
+    pub fn status_effect(&self) -> Option<StatusEffectKind> {
+        match self {
+            HazardKind::RockOutcrop => None,
+            HazardKind::SpinningBlade => Some(StatusEffectKind::Bleeding),
+            HazardKind::ElectrifiedCable => Some(StatusEffectKind::Stunned),
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use super::HAZARD_SPAWN_PERIOD;
+
+use bevy::prelude::*;
+
+#[derive(Resource)]
+pub struct HazardSpawnTimer {
+    pub timer: Timer,
+}
+
+impl Default for HazardSpawnTimer {
+    fn default() -> Self {
+        HazardSpawnTimer {
+            timer: Timer::from_seconds(HAZARD_SPAWN_PERIOD, TimerMode::Repeating),
+        }
+    }
+}
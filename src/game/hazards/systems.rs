@@ -0,0 +1,213 @@
+use super::components::Hazard;
+use super::resources::HazardSpawnTimer;
+use super::*;
+use crate::game::biomes::resources::BiomeState;
+use crate::game::components::Collider;
+use crate::game::enemy::components::{Enemy, EnemyKnockback};
+use crate::game::events::{
+    ChainsawCutHazardEvent, ChainsawDeflectedEvent, DamageSource, EnemyTakeDamageEvent,
+    PlayerDamageSource, PlayerTakeDamageEvent,
+};
+use crate::game::player::components::Player;
+use crate::game::player::{PlayerState, PLAYER_DAMAGE, PLAYER_DAMAGE_SPEED};
+use crate::game::{ArenaBounds, GameInfo, RunRng};
+
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::collide;
+use rand::prelude::*;
+
+pub fn tick_hazard_spawn_timer(mut hazard_spawn_timer: ResMut<HazardSpawnTimer>, time: Res<Time>) {
+    hazard_spawn_timer.timer.tick(time.delta());
+}
+
+// Hazards come from the pool of the current biome, and stay away from the boss fight
+pub fn spawn_hazards_over_time(
+    mut commands: Commands,
+    hazard_spawn_timer: Res<HazardSpawnTimer>,
+    biome_state: Res<BiomeState>,
+    game_info: Res<GameInfo>,
+    arena_bounds: Res<ArenaBounds>,
+    mut run_rng: ResMut<RunRng>,
+) {
+    if !hazard_spawn_timer.timer.just_finished() || game_info.is_boss_spawned {
+        return;
+    }
+
+    let rng = &mut *run_rng;
+
+    let Ok((kind, _)) = biome_state
+        .biome()
+        .hazard_pool
+        .choose_weighted(rng, |(_, weight)| *weight)
+    else {
+        return;
+    };
+
+    let (size, color) = match kind {
+        HazardKind::RockOutcrop => (
+            Vec2::new(rng.gen_range(ROCK_OUTCROP_LENGTH), ROCK_OUTCROP_THICKNESS),
+            ROCK_OUTCROP_COLOR,
+        ),
+        HazardKind::SpinningBlade => (SPINNING_BLADE_SIZE, SPINNING_BLADE_COLOR),
+        HazardKind::ElectrifiedCable => (
+            Vec2::new(
+                rng.gen_range(ELECTRIFIED_CABLE_LENGTH) * arena_bounds.width(),
+                ELECTRIFIED_CABLE_THICKNESS,
+            ),
+            ELECTRIFIED_CABLE_COLOR,
+        ),
+    };
+
+    // Outcrops and cables stick out of one of the side walls, blades are anywhere
+    let x = match kind {
+        HazardKind::SpinningBlade => arena_bounds.random_x(size, rng),
+        _ if rng.gen::<bool>() => arena_bounds.min.x + size.x / 2.0,
+        _ => arena_bounds.max.x - size.x / 2.0,
+    };
+
+    commands.spawn((
+        SpriteBundle {
+            transform: Transform::from_xyz(x, arena_bounds.min.y - size.y, 0.0),
+            sprite: Sprite {
+                color,
+                custom_size: Some(size),
+                ..default()
+            },
+            ..default()
+        },
+        Hazard {
+            kind: *kind,
+            collider: Collider { size },
+            health: kind.health(),
+        },
+    ));
+}
+
+pub fn move_hazards_vertically(
+    mut hazards_query: Query<&mut Transform, With<Hazard>>,
+    time: Res<Time>,
+) {
+    for mut hazard_transform in hazards_query.iter_mut() {
+        hazard_transform.translation.y += HAZARD_SPEED * time.delta_seconds();
+    }
+}
+
+// Blades spin and cables flicker
+pub fn animate_hazards(
+    mut hazards_query: Query<(&mut Transform, &mut Sprite, &Hazard)>,
+    time: Res<Time>,
+) {
+    for (mut hazard_transform, mut hazard_sprite, hazard) in hazards_query.iter_mut() {
+        match hazard.kind {
+            HazardKind::SpinningBlade => {
+                hazard_transform.rotate_z(SPINNING_BLADE_ROTATION_SPEED * time.delta_seconds());
+            }
+            HazardKind::ElectrifiedCable => {
+                let flicker = (time.elapsed_seconds() * ELECTRIFIED_CABLE_FLICKER_SPEED).sin();
+                hazard_sprite.color.set_a(0.75 + 0.25 * flicker);
+            }
+            HazardKind::RockOutcrop => {}
+        }
+    }
+}
+
+pub fn despawn_hazards(
+    mut commands: Commands,
+    hazards_query: Query<(Entity, &Transform, &Hazard)>,
+    arena_bounds: Res<ArenaBounds>,
+) {
+    for (hazard_entity, hazard_transform, hazard) in hazards_query.iter() {
+        if hazard_transform.translation.y > arena_bounds.max.y + hazard.collider.size.y {
+            commands.entity(hazard_entity).despawn();
+        }
+    }
+}
+
+// Hazards hurt the player in the regular form.
+// Chainsaw cuts through blades and cables, but heats up faster, and glances off the rocks.
+#[allow(clippy::too_many_arguments)]
+pub fn check_player_hazard_collision(
+    mut commands: Commands,
+    mut player_take_damage_event_writer: EventWriter<PlayerTakeDamageEvent>,
+    mut chainsaw_cut_hazard_event_writer: EventWriter<ChainsawCutHazardEvent>,
+    mut chainsaw_deflected_event_writer: EventWriter<ChainsawDeflectedEvent>,
+    mut hazards_query: Query<(Entity, &Transform, &mut Hazard)>,
+    player_query: Query<(&Transform, &Player)>,
+    player_state: Res<State<PlayerState>>,
+    time: Res<Time>,
+) {
+    let Ok((player_transform, player_struct)) = player_query.get_single() else {
+        return;
+    };
+
+    for (hazard_entity, hazard_transform, mut hazard) in hazards_query.iter_mut() {
+        if collide(
+            player_transform.translation,
+            player_struct.collider.size,
+            hazard_transform.translation,
+            hazard.collider.size,
+        )
+        .is_none()
+        {
+            continue;
+        }
+
+        match player_state.0 {
+            PlayerState::REGULAR => {
+                player_take_damage_event_writer.send(PlayerTakeDamageEvent {
+                    source: PlayerDamageSource::Hazard,
+                });
+                return;
+            }
+            PlayerState::DAMAGED => return,
+            PlayerState::CHAINSAW => {
+                let heat_multiplier = hazard.kind.cut_heat_multiplier();
+
+                let Some(health) = hazard.health.as_mut() else {
+                    let direction = (player_transform.translation - hazard_transform.translation)
+                        .normalize_or_zero();
+                    chainsaw_deflected_event_writer.send(ChainsawDeflectedEvent { direction });
+                    continue;
+                };
+
+                *health -= PLAYER_DAMAGE as f32 * PLAYER_DAMAGE_SPEED * time.delta_seconds();
+                chainsaw_cut_hazard_event_writer.send(ChainsawCutHazardEvent { heat_multiplier });
+
+                if *health <= 0.0 {
+                    commands.entity(hazard_entity).despawn();
+                }
+            }
+        }
+    }
+}
+
+// Enemies are hurt only when knocked into a hazard, and only once per knockback
+pub fn check_knocked_enemy_hazard_collision(
+    mut commands: Commands,
+    mut enemy_take_damage_event_writer: EventWriter<EnemyTakeDamageEvent>,
+    enemies_query: Query<(Entity, &Transform, &Enemy), With<EnemyKnockback>>,
+    hazards_query: Query<(&Transform, &Hazard)>,
+) {
+    for (enemy_entity, enemy_transform, enemy_struct) in enemies_query.iter() {
+        let Some(hazard) = hazards_query.iter().find_map(|(hazard_transform, hazard)| {
+            collide(
+                enemy_transform.translation,
+                enemy_struct.collider.size,
+                hazard_transform.translation,
+                hazard.collider.size,
+            )
+            .map(|_| hazard)
+        }) else {
+            continue;
+        };
+
+        enemy_take_damage_event_writer.send(EnemyTakeDamageEvent {
+            enemy_entity,
+            damage: HAZARD_IMPACT_DAMAGE,
+            source: DamageSource::Hazard,
+            status_effect: hazard.kind.status_effect(),
+            is_critical: false,
+        });
+        commands.entity(enemy_entity).remove::<EnemyKnockback>();
+    }
+}
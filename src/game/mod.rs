@@ -1,57 +1,113 @@
+pub mod biomes;
+pub mod combo;
 mod components;
+pub mod damage_numbers;
 pub mod enemy;
 pub mod events;
+pub mod hazards;
+pub mod pickups;
 pub mod player;
+pub mod run_stats;
+pub mod status_effects;
 mod systems;
 
 use std::collections::VecDeque;
 
+use biomes::BiomesPlugin;
+use combo::ComboPlugin;
+use damage_numbers::DamageNumbersPlugin;
 use enemy::EnemyPlugin;
 use events::*;
+use hazards::HazardsPlugin;
+use pickups::PickupsPlugin;
 use player::PlayerPlugin;
+use run_stats::RunStatsPlugin;
+use status_effects::StatusEffectsPlugin;
 use systems::*;
 
 use bevy::prelude::*;
+use rand::prelude::*;
 
 use self::enemy::EnemyType;
 
 const PICKUP_SPEED: f32 = 100.0;
 
 const PICKUP_SPAWN_PERIOD: f32 = 5.;
-const HEALTH_SPAWN_CHANCE: f32 = 0.4;
+// One pickup always spawns, the second one with this chance
+const BONUS_PICKUP_SPAWN_CHANCE: f32 = 0.6;
+// Random spawn positions tried, before settling for the one farthest from the enemies
+const PICKUP_SPAWN_ATTEMPTS: usize = 8;
+// Enemies closer than this to the bottom edge block the pickup spawn position
+const PICKUP_ENEMY_CLEARANCE: f32 = 150.0;
 
-const FUEL_PICKUP_SPRITE_SIZE: Vec2 = Vec2::new(19.0 * 1.5, 27.0 * 1.5);
-const FUEL_PICKUP_COLLIDER_SIZE: Vec2 = Vec2::new(19.0 * 1.5, 27.0 * 1.5);
-
-const HEALTH_PICKUP_COLLIDER_SIZE: Vec2 = Vec2::new(64.0, 64.0);
+pub const PARALLAX_SPEED: f32 = 1000.0;
 
-const FUEL_PICKUP_RESTORE: f32 = 25.0;
-const HEALTH_PICKUP_RESTORE: usize = 1;
+const LASER_CHARGE_WIDTH: f32 = 2.0;
+const LASER_CHARGE_COLOR: Color = Color::rgba(1.0, 0.3, 0.3, 0.6);
+const LASER_FIRE_COLOR: Color = Color::rgba(1.0, 0.55, 0.35, 0.9);
 
-pub const PARALLAX_SPEED: f32 = 1000.0;
-pub const BACKGROUND_LIGHTNESS: f32 = 0.5;
+// Logical size of the arena. The camera scales it to fit the window.
+pub const ARENA_SIZE: Vec2 = Vec2::new(260.0 * 2.0, 320.0 * 2.0);
 
 pub const MAX_DEPTH: f32 = 205.0;
 pub const MAX_ENEMIES_NUM: usize = 6;
 pub const PLAYER_FALLING_SPEED: f32 = 1.5;
 
+// === Endless mode ===
+// Past MAX_DEPTH the enemies get one more base health every this much depth
+const ENDLESS_RAMP_DEPTH: f32 = 150.0;
+// Ramped speed and spawn rate are capped, so the game stays playable
+const ENDLESS_MAX_SPEED_MULTIPLIER: f32 = 1.6;
+const ENDLESS_MAX_SPAWN_RATE_MULTIPLIER: f32 = 2.5;
+
+// How much harder the enemies are at this depth, only grows past MAX_DEPTH in the endless mode
+pub fn endless_ramp(depth: f32) -> f32 {
+    1.0 + (depth - MAX_DEPTH).max(0.0) / ENDLESS_RAMP_DEPTH
+}
+
+pub fn endless_speed_multiplier(depth: f32) -> f32 {
+    endless_ramp(depth).sqrt().min(ENDLESS_MAX_SPEED_MULTIPLIER)
+}
+
+pub fn endless_spawn_rate_multiplier(depth: f32) -> f32 {
+    endless_ramp(depth).min(ENDLESS_MAX_SPAWN_RATE_MULTIPLIER)
+}
+
 pub struct GamePlugin;
 
 impl Plugin for GamePlugin {
     fn build(&self, app: &mut App) {
         app.add_plugin(PlayerPlugin)
             .add_plugin(EnemyPlugin)
+            .add_plugin(StatusEffectsPlugin)
+            .add_plugin(DamageNumbersPlugin)
+            .add_plugin(ComboPlugin)
+            .add_plugin(RunStatsPlugin)
+            .add_plugin(BiomesPlugin)
+            .add_plugin(HazardsPlugin)
+            .add_plugin(PickupsPlugin)
             .add_event::<PlayerTakeDamageEvent>()
+            .add_event::<PlayerLostHealthEvent>()
             .add_event::<GameOverEvent>()
             .add_event::<EnemyTakeDamageEvent>()
+            .add_event::<EnemyKilledEvent>()
             .add_event::<PlayerTransitionToRegularFormEvent>()
             .add_event::<ChainsawFireWave>()
+            .add_event::<ChainsawDeflectedEvent>()
+            .add_event::<ChainsawCutHazardEvent>()
+            .add_event::<DamageNumberEvent>()
+            .add_event::<ComboHitEvent>()
             .init_resource::<GameInfo>()
+            .init_resource::<ArenaBounds>()
+            .init_resource::<RunRng>()
+            // Letterbox bars around the arena
+            .insert_resource(ClearColor(Color::BLACK))
             .init_resource::<PickupSpawnTimer>()
             // Run these upon start of the game
             .add_startup_system(spawn_camera)
             .add_startup_system(play_music)
             .add_system(spawn_parallax_background.in_schedule(OnExit(GameState::MainMenu)))
+            .add_system(apply_difficulty.in_schedule(OnExit(GameState::MainMenu)))
             // Run these while the game is running
             .add_systems(
                 (
@@ -62,18 +118,35 @@ impl Plugin for GamePlugin {
                     move_parallax_background,
                     handle_game_over_event,
                     get_cursor_world_coordinates,
+                    steer_homing_projectiles.before(handle_projectiles),
+                    accelerate_projectiles.before(handle_projectiles),
                     handle_projectiles,
+                    apply_projectile_sine_motion.after(handle_projectiles),
+                    handle_lasers,
                 )
                     .in_set(OnUpdate(GameState::Running)),
+            )
+            .add_system(
+                toggle_pause
+                    .run_if(in_state(GameState::Running).or_else(in_state(GameState::Paused))),
             );
     }
 }
 
+// Projectiles hurt only the other side
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Faction {
+    #[default]
+    Enemy,
+    Player,
+}
+
 #[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub enum GameState {
     #[default]
     MainMenu,
     Running,
+    Paused,
     Gameover,
 }
 
@@ -81,12 +154,121 @@ pub enum GameState {
 #[derive(Resource, Default)]
 pub struct GameInfo {
     pub cursor_position: Vec2,
+    // World position of the finger, which steers the player
+    pub touch_position: Option<Vec2>,
     pub player_progress: f32,
     pub enemies_num: usize,
+    // Depends on the difficulty, set when the run starts
+    pub max_enemies_num: usize,
     pub enemies_spawn_queue: VecDeque<EnemyType>,
     pub is_boss_spawned: bool,
 }
 
+// Every gameplay roll of the run (spawns, pickups, fire spread, boss patterns) is drawn from it,
+// so a run can be replayed from its seed. Cosmetic rolls keep using thread_rng.
+#[derive(Resource)]
+pub struct RunRng {
+    seed: u64,
+    rng: StdRng,
+}
+
+impl Default for RunRng {
+    fn default() -> Self {
+        RunRng::new(thread_rng().gen())
+    }
+}
+
+impl RunRng {
+    pub fn new(seed: u64) -> Self {
+        RunRng {
+            seed,
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+}
+
+impl RngCore for RunRng {
+    fn next_u32(&mut self) -> u32 {
+        self.rng.next_u32()
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.rng.next_u64()
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.rng.fill_bytes(dest)
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+        self.rng.try_fill_bytes(dest)
+    }
+}
+
+// Part of the world, where the game happens. Every bounds check reads it
+// instead of the window, so resizing the window doesn't change the game.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct ArenaBounds {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Default for ArenaBounds {
+    fn default() -> Self {
+        ArenaBounds {
+            min: Vec2::ZERO,
+            max: ARENA_SIZE,
+        }
+    }
+}
+
+impl ArenaBounds {
+    pub fn width(&self) -> f32 {
+        self.max.x - self.min.x
+    }
+
+    pub fn height(&self) -> f32 {
+        self.max.y - self.min.y
+    }
+
+    pub fn center(&self) -> Vec2 {
+        (self.min + self.max) / 2.0
+    }
+
+    // Returns the bounds moved inwards by the margin on every side
+    pub fn shrink(&self, margin: Vec2) -> ArenaBounds {
+        ArenaBounds {
+            min: self.min + margin,
+            max: self.max - margin,
+        }
+    }
+
+    pub fn contains(&self, point: Vec3) -> bool {
+        point.x >= self.min.x
+            && point.x <= self.max.x
+            && point.y >= self.min.y
+            && point.y <= self.max.y
+    }
+
+    // Moves the point inside of the bounds, keeping its z
+    pub fn clamp(&self, point: Vec3) -> Vec3 {
+        Vec3::new(
+            point.x.clamp(self.min.x, self.max.x),
+            point.y.clamp(self.min.y, self.max.y),
+            point.z,
+        )
+    }
+
+    // Random x along the arena, so an object of the given size fits into it
+    pub fn random_x(&self, size: Vec2, rng: &mut impl Rng) -> f32 {
+        self.min.x + rng.gen::<f32>() * (self.width() - size.x)
+    }
+}
+
 #[derive(Resource)]
 pub struct PickupSpawnTimer {
     timer: Timer,
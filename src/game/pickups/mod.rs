@@ -0,0 +1,233 @@
+pub mod resources;
+mod systems;
+
+use super::biomes::Biome;
+use super::player::resources::PlayerInfo;
+use super::player::PLAYER_FUEL_CAPACITY;
+use super::GameState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+// Weight of the fuel and health grows up to this many times, as the player runs out of them
+const PICKUP_NEED_WEIGHT_BONUS: f32 = 2.0;
+// Pickups spawn up to this much faster, while the player is low on fuel or health
+const PICKUP_NEED_RATE_BONUS: f32 = 0.5;
+
+// === Fuel ===
+const FUEL_PICKUP_SPRITE_SIZE: Vec2 = Vec2::new(19.0 * 1.5, 27.0 * 1.5);
+const FUEL_PICKUP_COLLIDER_SIZE: Vec2 = Vec2::new(19.0 * 1.5, 27.0 * 1.5);
+const FUEL_PICKUP_RESTORE: f32 = 25.0;
+
+// === Health ===
+const HEALTH_PICKUP_COLLIDER_SIZE: Vec2 = Vec2::new(64.0, 64.0);
+const HEALTH_PICKUP_RESTORE: usize = 1;
+
+// === Shield, coolant, magnet and chrono ===
+const POWER_UP_SPRITE_SIZE: Vec2 = Vec2::new(32.0, 32.0);
+const POWER_UP_COLLIDER_SIZE: Vec2 = Vec2::new(40.0, 40.0);
+const MAGNET_RADIUS: f32 = 220.0;
+const MAGNET_PULL_SPEED: f32 = 320.0;
+const CHRONO_PROJECTILE_SPEED_MULTIPLIER: f32 = 0.4;
+
+pub const PICKUPS: [PickupDefinition; 6] = [
+    PickupDefinition {
+        kind: PickupKind::Fuel,
+        name: "Fuel",
+        sprite: "sprites/fuel.png",
+        sprite_size: Some(FUEL_PICKUP_SPRITE_SIZE),
+        collider_size: FUEL_PICKUP_COLLIDER_SIZE,
+        spawn_weight: 1.5,
+        pity_spawns: Some(2),
+        effect: PickupEffect::RestoreFuel(FUEL_PICKUP_RESTORE),
+        duration: PickupDuration::Instant,
+    },
+    PickupDefinition {
+        kind: PickupKind::Health,
+        name: "Health",
+        sprite: "sprites/health.png",
+        sprite_size: None,
+        collider_size: HEALTH_PICKUP_COLLIDER_SIZE,
+        spawn_weight: 0.35,
+        pity_spawns: Some(6),
+        effect: PickupEffect::RestoreHealth(HEALTH_PICKUP_RESTORE),
+        duration: PickupDuration::Instant,
+    },
+    PickupDefinition {
+        kind: PickupKind::Shield,
+        name: "Shield",
+        sprite: "sprites/shield_pickup.png",
+        sprite_size: Some(POWER_UP_SPRITE_SIZE),
+        collider_size: POWER_UP_COLLIDER_SIZE,
+        spawn_weight: 0.15,
+        pity_spawns: None,
+        effect: PickupEffect::Shield,
+        duration: PickupDuration::UntilHit,
+    },
+    PickupDefinition {
+        kind: PickupKind::Coolant,
+        name: "Coolant",
+        sprite: "sprites/coolant_pickup.png",
+        sprite_size: Some(POWER_UP_SPRITE_SIZE),
+        collider_size: POWER_UP_COLLIDER_SIZE,
+        spawn_weight: 0.15,
+        pity_spawns: None,
+        effect: PickupEffect::Coolant,
+        duration: PickupDuration::Seconds(5.0),
+    },
+    PickupDefinition {
+        kind: PickupKind::Magnet,
+        name: "Magnet",
+        sprite: "sprites/magnet_pickup.png",
+        sprite_size: Some(POWER_UP_SPRITE_SIZE),
+        collider_size: POWER_UP_COLLIDER_SIZE,
+        spawn_weight: 0.15,
+        pity_spawns: None,
+        effect: PickupEffect::Magnet {
+            radius: MAGNET_RADIUS,
+            pull_speed: MAGNET_PULL_SPEED,
+        },
+        duration: PickupDuration::Seconds(8.0),
+    },
+    PickupDefinition {
+        kind: PickupKind::Chrono,
+        name: "Chrono",
+        sprite: "sprites/clock.png",
+        sprite_size: Some(POWER_UP_SPRITE_SIZE),
+        collider_size: POWER_UP_COLLIDER_SIZE,
+        spawn_weight: 0.15,
+        pity_spawns: None,
+        effect: PickupEffect::SlowTime {
+            projectile_speed_multiplier: CHRONO_PROJECTILE_SPEED_MULTIPLIER,
+        },
+        duration: PickupDuration::Seconds(5.0),
+    },
+];
+
+// PickupKind indexes PICKUPS, so the table has to follow the order of the enum
+const _: () = {
+    let mut index = 0;
+    while index < PICKUPS.len() {
+        assert!(
+            PICKUPS[index].kind as usize == index,
+            "PICKUPS must follow the order of PickupKind"
+        );
+        index += 1;
+    }
+};
+
+pub struct PickupsPlugin;
+
+impl Plugin for PickupsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ActivePickupEffects>()
+            .init_resource::<PickupPity>()
+            .add_event::<PickupCollectedEvent>()
+            .add_system(reset_pickups.in_schedule(OnExit(GameState::MainMenu)))
+            .add_systems(
+                (
+                    apply_pickup_effects,
+                    tick_pickup_effects.after(apply_pickup_effects),
+                    pull_pickups_with_magnet,
+                    slow_enemies_with_chrono,
+                )
+                    .in_set(OnUpdate(GameState::Running)),
+            );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickupKind {
+    Fuel,
+    Health,
+    Shield,
+    Coolant,
+    Magnet,
+    Chrono,
+}
+
+impl PickupKind {
+    pub fn definition(&self) -> &'static PickupDefinition {
+        &PICKUPS[*self as usize]
+    }
+}
+
+// What happens, when the player collects the pickup
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickupEffect {
+    RestoreFuel(f32),
+    RestoreHealth(usize),
+    // Blocks the next hit, however long it takes
+    Shield,
+    // Resets the chainsaw heat and keeps it from rising
+    Coolant,
+    // Pulls the nearby pickups to the player
+    Magnet { radius: f32, pull_speed: f32 },
+    // Freezes the enemies on collection, then slows them and their projectiles
+    SlowTime { projectile_speed_multiplier: f32 },
+}
+
+pub struct PickupDefinition {
+    pub kind: PickupKind,
+    pub name: &'static str,
+    pub sprite: &'static str,
+    // None keeps the size of the image
+    pub sprite_size: Option<Vec2>,
+    pub collider_size: Vec2,
+    // Biomes multiply it by their own weights
+    pub spawn_weight: f32,
+    // Spawned for sure, after this many spawns without it
+    pub pity_spawns: Option<u32>,
+    pub effect: PickupEffect,
+    // Lasting effects are shown in the HUD
+    pub duration: PickupDuration,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PickupDuration {
+    Instant,
+    Seconds(f32),
+    // Lasts until the player gets hit
+    UntilHit,
+}
+
+impl PickupDefinition {
+    // How badly the player needs the pickup, from 0 to 1
+    pub fn need(&self, player_info: &PlayerInfo) -> f32 {
+        match self.effect {
+            PickupEffect::RestoreFuel(_) => 1.0 - player_info.current_fuel / PLAYER_FUEL_CAPACITY,
+            PickupEffect::RestoreHealth(_) => {
+                1.0 - player_info.current_hp as f32 / player_info.max_hp.max(1) as f32
+            }
+            _ => 0.0,
+        }
+        .clamp(0.0, 1.0)
+    }
+
+    pub fn spawn_weight_in(&self, biome: &Biome, player_info: &PlayerInfo) -> f32 {
+        let biome_multiplier = biome
+            .pickup_pool
+            .iter()
+            .find(|(kind, _)| *kind == self.kind)
+            .map_or(1.0, |(_, multiplier)| *multiplier);
+
+        self.spawn_weight
+            * biome_multiplier
+            * (1.0 + self.need(player_info) * PICKUP_NEED_WEIGHT_BONUS)
+    }
+}
+
+// Pickup spawn timer ticks faster, when the player needs fuel or health
+pub fn pickup_spawn_rate_multiplier(player_info: &PlayerInfo) -> f32 {
+    let need = PICKUPS
+        .iter()
+        .map(|pickup| pickup.need(player_info))
+        .fold(0.0, f32::max);
+
+    1.0 + need * PICKUP_NEED_RATE_BONUS
+}
+
+pub struct PickupCollectedEvent {
+    pub pickup: &'static PickupDefinition,
+}
@@ -0,0 +1,93 @@
+use super::{PickupDefinition, PickupDuration, PickupEffect, PickupKind, PICKUPS};
+use crate::game::player::resources::PlayerInfo;
+
+use bevy::prelude::*;
+
+// Effects of the collected pickups, which last for a while
+#[derive(Resource, Default)]
+pub struct ActivePickupEffects {
+    pub effects: Vec<ActivePickupEffect>,
+}
+
+pub struct ActivePickupEffect {
+    pub pickup: &'static PickupDefinition,
+    // None for the effects, which last until the player gets hit
+    pub timer: Option<Timer>,
+}
+
+impl ActivePickupEffects {
+    // Collecting the same pickup again restarts its timer
+    pub fn activate(&mut self, pickup: &'static PickupDefinition) {
+        let timer = match pickup.duration {
+            PickupDuration::Instant => return,
+            PickupDuration::Seconds(duration) => {
+                Some(Timer::from_seconds(duration, TimerMode::Once))
+            }
+            PickupDuration::UntilHit => None,
+        };
+
+        self.effects
+            .retain(|active_effect| active_effect.pickup.kind != pickup.kind);
+        self.effects.push(ActivePickupEffect { pickup, timer });
+    }
+
+    pub fn get(&self, kind: PickupKind) -> Option<&ActivePickupEffect> {
+        self.effects
+            .iter()
+            .find(|active_effect| active_effect.pickup.kind == kind)
+    }
+
+    pub fn is_active(&self, kind: PickupKind) -> bool {
+        self.get(kind).is_some()
+    }
+
+    // Ends the effect early, returns whether it was active
+    pub fn consume(&mut self, kind: PickupKind) -> bool {
+        let effects_num = self.effects.len();
+        self.effects
+            .retain(|active_effect| active_effect.pickup.kind != kind);
+
+        self.effects.len() != effects_num
+    }
+
+    // Enemy projectiles are slower, while the chrono pickup lasts
+    pub fn projectile_speed_multiplier(&self) -> f32 {
+        match self
+            .get(PickupKind::Chrono)
+            .map(|active_effect| active_effect.pickup.effect)
+        {
+            Some(PickupEffect::SlowTime {
+                projectile_speed_multiplier,
+            }) => projectile_speed_multiplier,
+            _ => 1.0,
+        }
+    }
+}
+
+// Pickup spawns since every pickup was spawned last time, indexed by PickupKind
+#[derive(Resource, Default)]
+pub struct PickupPity {
+    pub droughts: [u32; PICKUPS.len()],
+}
+
+impl PickupPity {
+    // Called once per pickup spawn
+    pub fn tick(&mut self) {
+        for drought in self.droughts.iter_mut() {
+            *drought += 1;
+        }
+    }
+
+    pub fn reset(&mut self, kind: PickupKind) {
+        self.droughts[kind as usize] = 0;
+    }
+
+    // Pickup, which hasn't spawned for too long, while the player needs it
+    pub fn due(&self, player_info: &PlayerInfo) -> Option<&'static PickupDefinition> {
+        PICKUPS.iter().find(|pickup| {
+            pickup.pity_spawns.is_some_and(|pity_spawns| {
+                self.droughts[pickup.kind as usize] > pity_spawns && pickup.need(player_info) > 0.0
+            })
+        })
+    }
+}
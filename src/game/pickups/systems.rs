@@ -0,0 +1,138 @@
+use super::resources::{ActivePickupEffects, PickupPity};
+use super::{PickupCollectedEvent, PickupEffect, PickupKind};
+use crate::game::components::Pickup;
+use crate::game::damage_numbers::DamageNumberKind;
+use crate::game::enemy::components::Enemy;
+use crate::game::events::DamageNumberEvent;
+use crate::game::player::components::Player;
+use crate::game::player::resources::PlayerInfo;
+use crate::game::player::PLAYER_FUEL_CAPACITY;
+use crate::game::run_stats::resources::RunStats;
+use crate::game::status_effects::components::StatusEffects;
+use crate::game::status_effects::StatusEffectKind;
+
+use bevy::prelude::*;
+
+pub fn reset_pickups(
+    mut active_pickup_effects: ResMut<ActivePickupEffects>,
+    mut pickup_pity: ResMut<PickupPity>,
+) {
+    active_pickup_effects.effects.clear();
+    *pickup_pity = PickupPity::default();
+}
+
+pub fn apply_pickup_effects(
+    mut pickup_collected_event_reader: EventReader<PickupCollectedEvent>,
+    mut damage_number_event_writer: EventWriter<DamageNumberEvent>,
+    mut active_pickup_effects: ResMut<ActivePickupEffects>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut run_stats: ResMut<RunStats>,
+    mut player_query: Query<(Entity, &Transform, &mut Sprite), With<Player>>,
+    mut enemy_query: Query<&mut StatusEffects, With<Enemy>>,
+) {
+    let Ok((player_entity, player_transform, mut player_sprite)) = player_query.get_single_mut()
+    else {
+        return;
+    };
+
+    for event in pickup_collected_event_reader.iter() {
+        match event.pickup.effect {
+            PickupEffect::RestoreFuel(amount) => {
+                let previous_fuel = player_info.current_fuel;
+                player_info.current_fuel =
+                    (player_info.current_fuel + amount).min(PLAYER_FUEL_CAPACITY);
+                run_stats.fuel_collected += player_info.current_fuel - previous_fuel;
+            }
+            PickupEffect::RestoreHealth(amount) => {
+                let previous_hp = player_info.current_hp;
+                player_info.current_hp = (player_info.current_hp + amount).min(player_info.max_hp);
+
+                if player_info.current_hp > previous_hp {
+                    damage_number_event_writer.send(DamageNumberEvent {
+                        target: Some(player_entity),
+                        position: player_transform.translation,
+                        amount: (player_info.current_hp - previous_hp) as f32,
+                        kind: DamageNumberKind::Heal,
+                    });
+                }
+            }
+            PickupEffect::Coolant => {
+                player_info.chainsaw_heat = 0.0;
+                player_sprite.color = Color::WHITE;
+            }
+            // Time stops for a moment, the enemies stay slowed after they thaw
+            PickupEffect::SlowTime { .. } => {
+                for mut status_effects in enemy_query.iter_mut() {
+                    status_effects.apply(StatusEffectKind::Frozen);
+                }
+            }
+            PickupEffect::Shield | PickupEffect::Magnet { .. } => {}
+        }
+
+        active_pickup_effects.activate(event.pickup);
+    }
+}
+
+pub fn tick_pickup_effects(
+    mut active_pickup_effects: ResMut<ActivePickupEffects>,
+    time: Res<Time>,
+) {
+    for timer in active_pickup_effects
+        .effects
+        .iter_mut()
+        .filter_map(|active_effect| active_effect.timer.as_mut())
+    {
+        timer.tick(time.delta());
+    }
+
+    active_pickup_effects.effects.retain(|active_effect| {
+        !active_effect
+            .timer
+            .as_ref()
+            .is_some_and(|timer| timer.finished())
+    });
+}
+
+pub fn pull_pickups_with_magnet(
+    active_pickup_effects: Res<ActivePickupEffects>,
+    player_query: Query<&Transform, With<Player>>,
+    mut pickups_query: Query<&mut Transform, (With<Pickup>, Without<Player>)>,
+    time: Res<Time>,
+) {
+    let Some(PickupEffect::Magnet { radius, pull_speed }) = active_pickup_effects
+        .get(PickupKind::Magnet)
+        .map(|active_effect| active_effect.pickup.effect)
+    else {
+        return;
+    };
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for mut pickup_transform in pickups_query.iter_mut() {
+        let to_player = player_transform.translation - pickup_transform.translation;
+        if to_player.length() <= radius {
+            pickup_transform.translation +=
+                to_player.normalize_or_zero() * pull_speed * time.delta_seconds();
+        }
+    }
+}
+
+// Slows every enemy until the chrono pickup ends, the enemies spawned meanwhile as well
+pub fn slow_enemies_with_chrono(
+    active_pickup_effects: Res<ActivePickupEffects>,
+    mut enemy_query: Query<&mut StatusEffects, With<Enemy>>,
+) {
+    let Some(remaining_secs) = active_pickup_effects
+        .get(PickupKind::Chrono)
+        .and_then(|chrono_effect| chrono_effect.timer.as_ref())
+        .map(|timer| timer.remaining_secs())
+    else {
+        return;
+    };
+
+    // Longer slow downs from elsewhere are kept, the shorter ones are extended
+    for mut status_effects in enemy_query.iter_mut() {
+        status_effects.apply_for(StatusEffectKind::Slowed, remaining_secs);
+    }
+}
@@ -7,6 +7,7 @@ use super::GameState;
 use systems::*;
 
 use bevy::prelude::*;
+use serde::Serialize;
 
 pub const PLAYER_MAX_HEALTH: usize = 5;
 pub const PLAYER_FUEL_CAPACITY: f32 = 100.0;
@@ -27,6 +28,16 @@ const PASSIVE_PLAYER_FUEL_GAIN_SPEED: f32 = 0.85;
 
 const CHAINSAW_HEAT_LIMIT: f32 = 65.0;
 const CHAINSAW_HEAT_SPEED: f32 = 100.0;
+// Grinding against a shield heats the chainsaw faster than cutting
+const CHAINSAW_DEFLECT_HEAT_MULTIPLIER: f32 = 3.0;
+const PLAYER_DEFLECT_KNOCKBACK_SPEED: f32 = 650.0;
+const PLAYER_KNOCKBACK_DAMPING: f32 = 8.0;
+
+const REFLECTED_PROJECTILE_SPEED_MULTIPLIER: f32 = 1.5;
+const REFLECTED_PROJECTILE_COLOR: Color = Color::rgb(0.5, 1.0, 0.6);
+
+// Mouse scheme stops steering when the cursor is this close to the player
+const PLAYER_CURSOR_DEAD_ZONE: f32 = 10.0;
 
 pub struct PlayerPlugin;
 
@@ -35,7 +46,16 @@ impl Plugin for PlayerPlugin {
         app.add_state::<PlayerState>()
             .init_resource::<PlayerInfo>()
             .init_resource::<PlayerDamageInvulnerabilityTimer>()
+            .init_resource::<PlayerIntent>()
+            .init_resource::<PlayerKnockback>()
             .add_system(spawn_player.in_schedule(OnExit(GameState::MainMenu)))
+            // Every control scheme fills the same PlayerIntent through the input actions
+            .add_system(
+                update_player_intent
+                    .before(move_player)
+                    .before(transition_to_player_chainsaw_state)
+                    .in_set(OnUpdate(GameState::Running)),
+            )
             .add_systems(
                 (
                     move_player,
@@ -48,18 +68,24 @@ impl Plugin for PlayerPlugin {
                     transition_to_player_regular_state,
                     drain_fuel.run_if(in_state(PlayerState::CHAINSAW)),
                     manage_chainsaw_overheat.run_if(in_state(PlayerState::CHAINSAW)),
+                    handle_chainsaw_deflected_event.before(move_player),
                     check_player_enemy_collision.run_if(not(in_state(PlayerState::DAMAGED))),
                     handle_player_take_damage_event.run_if(in_state(PlayerState::REGULAR)),
                     player_take_damage_invulnerability.run_if(in_state(PlayerState::DAMAGED)),
                     tick_damage_invulnerability_timer.run_if(in_state(PlayerState::DAMAGED)),
                 )
                     .in_set(OnUpdate(GameState::Running)),
+            )
+            .add_system(
+                check_player_laser_collision
+                    .run_if(in_state(PlayerState::REGULAR))
+                    .in_set(OnUpdate(GameState::Running)),
             );
     }
 }
 
 // TODO: add chainsaw overheat.
-#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize)]
 pub enum PlayerState {
     #[default]
     REGULAR,
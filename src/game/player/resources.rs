@@ -6,8 +6,14 @@ use super::{PLAYER_FUEL_CAPACITY, PLAYER_MAX_HEALTH, PLAYER_TAKE_DAMAGE_INVULNER
 pub struct PlayerInfo {
     pub current_fuel: f32,
     pub current_hp: usize,
+    // Depends on the difficulty
+    pub max_hp: usize,
     pub blood: usize,
+    // Depth plus the kills multiplied by the combo
+    pub score: usize,
     pub chainsaw_heat: f32,
+    // Enemies killed by the reflected projectiles
+    pub reflect_kills: usize,
 }
 
 impl Default for PlayerInfo {
@@ -15,8 +21,11 @@ impl Default for PlayerInfo {
         PlayerInfo {
             current_fuel: PLAYER_FUEL_CAPACITY,
             current_hp: PLAYER_MAX_HEALTH,
+            max_hp: PLAYER_MAX_HEALTH,
             blood: 0,
+            score: 0,
             chainsaw_heat: 0.0,
+            reflect_kills: 0,
         }
     }
 }
@@ -33,3 +42,18 @@ impl Default for PlayerDamageInvulnerabilityTimer {
         }
     }
 }
+
+// What the player wants to do this frame. Filled by the selected control scheme,
+// so movement and chainsaw systems don't care which device is used.
+#[derive(Resource, Default, Debug)]
+pub struct PlayerIntent {
+    // Direction scaled by the fraction of the player's current speed (length 0..=1)
+    pub movement: Vec3,
+    pub activate_chainsaw: bool,
+}
+
+// Velocity, which pushes the player regardless of the input (e.g. bouncing off a shield)
+#[derive(Resource, Default)]
+pub struct PlayerKnockback {
+    pub velocity: Vec3,
+}
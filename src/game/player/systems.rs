@@ -1,41 +1,46 @@
 use super::components::Player;
-use super::resources::PlayerDamageInvulnerabilityTimer;
+use super::resources::{PlayerDamageInvulnerabilityTimer, PlayerIntent, PlayerKnockback};
 use super::{
-    PlayerInfo, PlayerState, CHAINSAW_HEAT_LIMIT, CHAINSAW_HEAT_SPEED,
-    PLAYER_CHAINSAW_COLLIDER_SIZE,
+    PlayerInfo, PlayerState, CHAINSAW_DEFLECT_HEAT_MULTIPLIER, CHAINSAW_HEAT_LIMIT,
+    CHAINSAW_HEAT_SPEED, PLAYER_CHAINSAW_COLLIDER_SIZE, PLAYER_CURSOR_DEAD_ZONE,
+    PLAYER_DEFLECT_KNOCKBACK_SPEED, PLAYER_KNOCKBACK_DAMPING, REFLECTED_PROJECTILE_COLOR,
+    REFLECTED_PROJECTILE_SPEED_MULTIPLIER,
 };
 use super::{
     CHAINSAW_FUEL_DRAIN_SPEED, PASSIVE_PLAYER_FUEL_GAIN_AMOUNT, PASSIVE_PLAYER_FUEL_GAIN_SPEED,
-    PLAYER_CHAINSAW_SPEED, PLAYER_FUEL_CAPACITY, PLAYER_MAX_HEALTH, PLAYER_REGULAR_COLLIDER_SIZE,
-    PLAYER_REGULAR_SPEED,
+    PLAYER_CHAINSAW_SPEED, PLAYER_DAMAGE, PLAYER_DAMAGE_SPEED, PLAYER_FUEL_CAPACITY,
+    PLAYER_REGULAR_COLLIDER_SIZE, PLAYER_REGULAR_SPEED,
 };
-use crate::game::components::{Collider, FuelPickup, HealthPickup, Pickup, Projectile};
-use crate::game::enemy::components::Enemy;
+use crate::controls::resources::ActionState;
+use crate::controls::{InputAction, MoveInput};
+use crate::game::combo::resources::Combo;
+use crate::game::combo::SCORE_PER_DEPTH;
+use crate::game::components::{Collider, Homing, Laser, Pickup, Projectile, ProjectileSpeedCurve};
+use crate::game::enemy::components::{Enemy, Shielded};
 use crate::game::events::{
-    ChainsawFireWave, EnemyTakeDamageEvent, GameOverEvent, PlayerTakeDamageEvent,
-    PlayerTransitionToRegularFormEvent,
+    ChainsawCutHazardEvent, ChainsawDeflectedEvent, ChainsawFireWave, ComboHitEvent, DamageSource,
+    EnemyTakeDamageEvent, GameOverEvent, PlayerDamageSource, PlayerLostHealthEvent,
+    PlayerTakeDamageEvent, PlayerTransitionToRegularFormEvent,
 };
-use crate::game::{GameInfo, MAX_DEPTH, PLAYER_FALLING_SPEED};
-use crate::game::{FUEL_PICKUP_RESTORE, HEALTH_PICKUP_RESTORE};
+use crate::game::pickups::resources::ActivePickupEffects;
+use crate::game::pickups::{PickupCollectedEvent, PickupKind};
+use crate::game::run_stats::resources::RunStats;
+use crate::game::status_effects::StatusEffectKind;
+use crate::game::{ArenaBounds, Faction, GameInfo, MAX_DEPTH, PLAYER_FALLING_SPEED};
+use crate::settings::resources::GameSettings;
+use crate::settings::GameMode;
 
 use bevy::prelude::*;
 use bevy::sprite::collide_aabb::*;
-use bevy::window::PrimaryWindow;
 
 pub fn spawn_player(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena_bounds: Res<ArenaBounds>,
     asset_server: Res<AssetServer>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
-
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(
-                primary_window.width() / 2.0,
-                primary_window.height() / 2.0,
-                0.0,
-            ),
+            transform: Transform::from_translation(arena_bounds.center().extend(0.0)),
             texture: asset_server.load("sprites/player_falling.png"),
             ..default()
         },
@@ -52,19 +57,19 @@ pub fn spawn_player(
 // Transition to chainsaw state if the player:
 // 1. In the regular form
 // 2. Has maximum fuel
-// 3. Clicks the LMB
+// 3. Activates the chainsaw
 pub fn transition_to_player_chainsaw_state(
     mut next_player_state: ResMut<NextState<PlayerState>>,
     mut player_query: Query<(&mut Handle<Image>, &mut Player)>,
-    mouse_input: Res<Input<MouseButton>>,
+    mut run_stats: ResMut<RunStats>,
+    player_intent: Res<PlayerIntent>,
     player_info: Res<PlayerInfo>,
     asset_server: Res<AssetServer>,
 ) {
     if let Ok((mut player_texture, mut player)) = player_query.get_single_mut() {
-        if player_info.current_fuel == PLAYER_FUEL_CAPACITY
-            && mouse_input.just_pressed(MouseButton::Left)
-        {
+        if player_info.current_fuel == PLAYER_FUEL_CAPACITY && player_intent.activate_chainsaw {
             next_player_state.set(PlayerState::CHAINSAW);
+            run_stats.chainsaw_activations += 1;
             player.current_speed = PLAYER_CHAINSAW_SPEED;
 
             *player_texture = asset_server.load("sprites/player_chainsaw.png");
@@ -104,9 +109,12 @@ pub fn drain_fuel(
     mut player_transition_to_regular_form_event_writer: EventWriter<
         PlayerTransitionToRegularFormEvent,
     >,
+    mut run_stats: ResMut<RunStats>,
     time: Res<Time>,
 ) {
-    player_info.current_fuel -= CHAINSAW_FUEL_DRAIN_SPEED * time.delta_seconds();
+    let fuel_burned = CHAINSAW_FUEL_DRAIN_SPEED * time.delta_seconds();
+    player_info.current_fuel -= fuel_burned;
+    run_stats.fuel_burned += fuel_burned;
 
     if player_info.current_fuel < 1.0 {
         player_transition_to_regular_form_event_writer.send(PlayerTransitionToRegularFormEvent {});
@@ -122,46 +130,25 @@ pub fn gain_fuel_over_time(mut player_info: ResMut<PlayerInfo>, time: Res<Time>)
 
 pub fn check_player_pickup_collision(
     mut commands: Commands,
-    mut player_info: ResMut<PlayerInfo>,
+    mut pickup_collected_event_writer: EventWriter<PickupCollectedEvent>,
     player_query: Query<(&Transform, &Player)>,
-    fuel_query: Query<(Entity, &Transform, &Pickup), With<FuelPickup>>,
-    health_query: Query<(Entity, &Transform, &Pickup), With<HealthPickup>>,
+    pickups_query: Query<(Entity, &Transform, &Pickup)>,
 ) {
     if let Ok((player_transform, player_struct)) = player_query.get_single() {
-        for (fuel_entity, fuel_transform, fuel_struct) in fuel_query.iter() {
-            // If collided with fuel
-            if let Some(_) = collide(
+        for (pickup_entity, pickup_transform, pickup_struct) in pickups_query.iter() {
+            if collide(
                 player_transform.translation,
                 player_struct.collider.size,
-                fuel_transform.translation,
-                fuel_struct.collider.size,
-            ) {
-                player_info.current_fuel =
-                    if PLAYER_FUEL_CAPACITY < player_info.current_fuel + FUEL_PICKUP_RESTORE {
-                        PLAYER_FUEL_CAPACITY
-                    } else {
-                        player_info.current_fuel + FUEL_PICKUP_RESTORE
-                    };
-                commands.entity(fuel_entity).despawn();
-            }
-        }
-
-        for (health_entity, health_transform, health_struct) in health_query.iter() {
-            // If collided with heart
-            if let Some(_) = collide(
-                player_transform.translation,
-                player_struct.collider.size,
-                health_transform.translation,
-                health_struct.collider.size,
-            ) {
-                // Collided with health
-                player_info.current_hp =
-                    if PLAYER_MAX_HEALTH < player_info.current_hp + HEALTH_PICKUP_RESTORE {
-                        PLAYER_MAX_HEALTH
-                    } else {
-                        player_info.current_hp + HEALTH_PICKUP_RESTORE
-                    };
-                commands.entity(health_entity).despawn();
+                pickup_transform.translation,
+                pickup_struct.collider.size,
+            )
+            .is_some()
+            {
+                // Effects are applied by the pickups plugin
+                pickup_collected_event_writer.send(PickupCollectedEvent {
+                    pickup: pickup_struct.kind.definition(),
+                });
+                commands.entity(pickup_entity).despawn();
             }
         }
     }
@@ -173,12 +160,14 @@ pub fn check_player_pickup_collision(
 pub fn check_player_enemy_collision(
     mut player_take_damage_event_writer: EventWriter<PlayerTakeDamageEvent>,
     mut enemy_take_damage_event_writer: EventWriter<EnemyTakeDamageEvent>,
-    mut enemies_query: Query<(&Transform, Entity, &Enemy)>,
+    mut chainsaw_deflected_event_writer: EventWriter<ChainsawDeflectedEvent>,
+    mut enemies_query: Query<(&Transform, Entity, &Enemy, Option<&Shielded>)>,
     player_query: Query<(&Transform, &Player)>,
     player_state: Res<State<PlayerState>>,
+    time: Res<Time>,
 ) {
     if let Ok((player_transform, player_struct)) = player_query.get_single() {
-        for (enemy_transform, enemy_entity, enemy_struct) in enemies_query.iter_mut() {
+        for (enemy_transform, enemy_entity, enemy_struct, shielded) in enemies_query.iter_mut() {
             // If collided with enemy
             if let Some(_) = collide(
                 player_transform.translation,
@@ -189,7 +178,9 @@ pub fn check_player_enemy_collision(
                 // Check in which state player is
                 match player_state.0 {
                     PlayerState::REGULAR => {
-                        player_take_damage_event_writer.send(PlayerTakeDamageEvent {});
+                        player_take_damage_event_writer.send(PlayerTakeDamageEvent {
+                            source: PlayerDamageSource::EnemyContact,
+                        });
                         return;
                     }
                     // If the player already took damage
@@ -197,8 +188,34 @@ pub fn check_player_enemy_collision(
                         return;
                     }
                     // Send the event, when enemy takes damage
+                    // Shield deflects the chainsaw, but the back is more vulnerable
                     PlayerState::CHAINSAW => {
-                        enemy_take_damage_event_writer.send(EnemyTakeDamageEvent { enemy_entity });
+                        let direction = (player_transform.translation
+                            - enemy_transform.translation)
+                            .normalize_or_zero();
+
+                        match shielded
+                            .map_or(Some(1.0), |shielded| shielded.hit_multiplier(direction))
+                        {
+                            Some(damage_multiplier) => {
+                                enemy_take_damage_event_writer.send(EnemyTakeDamageEvent {
+                                    enemy_entity,
+                                    damage: PLAYER_DAMAGE as f32
+                                        * PLAYER_DAMAGE_SPEED
+                                        * damage_multiplier
+                                        * time.delta_seconds(),
+                                    source: DamageSource::Chainsaw,
+                                    // Hitting the enemy from behind makes it bleed
+                                    status_effect: (damage_multiplier > 1.0)
+                                        .then_some(StatusEffectKind::Bleeding),
+                                    is_critical: damage_multiplier > 1.0,
+                                });
+                            }
+                            None => {
+                                chainsaw_deflected_event_writer
+                                    .send(ChainsawDeflectedEvent { direction });
+                            }
+                        }
                     }
                 };
             }
@@ -206,14 +223,31 @@ pub fn check_player_enemy_collision(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn check_player_projectile_collision(
+    mut commands: Commands,
     mut player_take_damage_event_writer: EventWriter<PlayerTakeDamageEvent>,
-    mut projectiles_query: Query<(&Transform, &mut Projectile)>,
+    mut combo_hit_event_writer: EventWriter<ComboHitEvent>,
+    mut run_stats: ResMut<RunStats>,
+    mut projectiles_query: Query<(Entity, &Transform, &mut Projectile, &mut Sprite)>,
     player_query: Query<(&Transform, &Player)>,
     player_state: Res<State<PlayerState>>,
+    player_intent: Res<PlayerIntent>,
+    action_state: Res<ActionState>,
 ) {
     if let Ok((player_transform, player_struct)) = player_query.get_single() {
-        for (projectile_transform, mut projectile_struct) in projectiles_query.iter_mut() {
+        for (
+            projectile_entity,
+            projectile_transform,
+            mut projectile_struct,
+            mut projectile_sprite,
+        ) in projectiles_query.iter_mut()
+        {
+            // Reflected projectiles don't hurt the player
+            if projectile_struct.faction == Faction::Player {
+                continue;
+            }
+
             // If collided with projectile
             if let Some(_) = collide(
                 player_transform.translation,
@@ -224,16 +258,38 @@ pub fn check_player_projectile_collision(
                 // Check in which state player is
                 match player_state.0 {
                     PlayerState::REGULAR => {
-                        player_take_damage_event_writer.send(PlayerTakeDamageEvent {});
+                        player_take_damage_event_writer.send(PlayerTakeDamageEvent {
+                            source: PlayerDamageSource::Projectile,
+                        });
                         return;
                     }
                     // If the player already took damage
                     PlayerState::DAMAGED => {
                         continue;
                     }
-                    // Send projectiles backwards
+                    // Reflect projectiles to the cursor (or where the player is heading),
+                    // so they hurt the enemies
                     PlayerState::CHAINSAW => {
-                        projectile_struct.direction *= -1.;
+                        let aim_direction = match action_state.movement {
+                            MoveInput::Cursor(cursor_position) => (cursor_position.extend(0.0)
+                                - projectile_transform.translation)
+                                .try_normalize(),
+                            _ => player_intent.movement.try_normalize(),
+                        };
+
+                        projectile_struct.direction =
+                            aim_direction.unwrap_or(-projectile_struct.direction);
+                        projectile_struct.speed *= REFLECTED_PROJECTILE_SPEED_MULTIPLIER;
+                        projectile_struct.faction = Faction::Player;
+                        projectile_sprite.color = REFLECTED_PROJECTILE_COLOR;
+                        combo_hit_event_writer.send(ComboHitEvent);
+                        run_stats.projectiles_reflected += 1;
+
+                        // Behaviors, which would bring the projectile back to the player
+                        commands
+                            .entity(projectile_entity)
+                            .remove::<Homing>()
+                            .remove::<ProjectileSpeedCurve>();
                         continue;
                     }
                 };
@@ -242,23 +298,73 @@ pub fn check_player_projectile_collision(
     }
 }
 
-// Executes when enemy takes damage
+// Fired lasers hurt the player, the chainsaw blocks them (runs only in the regular state)
+pub fn check_player_laser_collision(
+    mut player_take_damage_event_writer: EventWriter<PlayerTakeDamageEvent>,
+    lasers_query: Query<(&Transform, &Laser)>,
+    player_query: Query<(&Transform, &Player)>,
+) {
+    if let Ok((player_transform, player_struct)) = player_query.get_single() {
+        for (laser_transform, laser) in lasers_query.iter() {
+            if laser.is_firing()
+                && laser.hits(
+                    laser_transform.translation,
+                    player_transform.translation,
+                    player_struct.collider.size,
+                )
+            {
+                player_take_damage_event_writer.send(PlayerTakeDamageEvent {
+                    source: PlayerDamageSource::Laser,
+                });
+                return;
+            }
+        }
+    }
+}
+
+// Executes when enemy takes damage, the chainsaw hits a shield or cuts a hazard
+#[allow(clippy::too_many_arguments)]
 pub fn manage_chainsaw_overheat(
     mut enemy_take_damage_event_reader: EventReader<EnemyTakeDamageEvent>,
+    mut chainsaw_deflected_event_reader: EventReader<ChainsawDeflectedEvent>,
+    mut chainsaw_cut_hazard_event_reader: EventReader<ChainsawCutHazardEvent>,
     mut fire_wave_event_writer: EventWriter<ChainsawFireWave>,
     mut player_info: ResMut<PlayerInfo>,
     mut player_query: Query<&mut Sprite, With<Player>>,
+    active_pickup_effects: Res<ActivePickupEffects>,
     time: Res<Time>,
 ) {
     if let Ok(mut player_sprite) = player_query.get_single_mut() {
-        for _ in enemy_take_damage_event_reader.iter() {
-            player_info.chainsaw_heat += CHAINSAW_HEAT_SPEED * time.delta_seconds();
+        let heat_multipliers = enemy_take_damage_event_reader
+            .iter()
+            .filter(|event| event.source == DamageSource::Chainsaw)
+            .map(|_| 1.0)
+            .chain(
+                chainsaw_deflected_event_reader
+                    .iter()
+                    .map(|_| CHAINSAW_DEFLECT_HEAT_MULTIPLIER),
+            )
+            .chain(
+                chainsaw_cut_hazard_event_reader
+                    .iter()
+                    .map(|event| event.heat_multiplier),
+            );
+
+        for heat_multiplier in heat_multipliers {
+            // Coolant keeps the chainsaw from heating up
+            if active_pickup_effects.is_active(PickupKind::Coolant) {
+                continue;
+            }
+
+            player_info.chainsaw_heat +=
+                CHAINSAW_HEAT_SPEED * heat_multiplier * time.delta_seconds();
             // println!("Chainsaw heat: {}", player_info.chainsaw_heat);
 
             // Gradually turn orange
             player_sprite.color = Color::rgb(
                 player_sprite.color.r(),
-                player_sprite.color.g() - 0.01 * CHAINSAW_HEAT_SPEED * time.delta_seconds(),
+                player_sprite.color.g()
+                    - 0.01 * CHAINSAW_HEAT_SPEED * heat_multiplier * time.delta_seconds(),
                 player_sprite.color.b(),
             );
 
@@ -272,54 +378,100 @@ pub fn manage_chainsaw_overheat(
     }
 }
 
+// Turns the input actions into the player intent.
+// Cursor steers at full speed, keys accelerate and decelerate, the stick is used as is.
+pub fn update_player_intent(
+    mut player_intent: ResMut<PlayerIntent>,
+    player_query: Query<&Transform, With<Player>>,
+    action_state: Res<ActionState>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    player_intent.movement = match action_state.movement {
+        MoveInput::Cursor(cursor_position) => {
+            let Ok(player_transform) = player_query.get_single() else {
+                return;
+            };
+            let destination = cursor_position.extend(0.0);
+
+            if player_transform.translation.distance(destination) > PLAYER_CURSOR_DEAD_ZONE {
+                (destination - player_transform.translation).normalize()
+            } else {
+                Vec3::ZERO
+            }
+        }
+        MoveInput::Digital(direction) => {
+            let target = direction.normalize_or_zero().extend(0.0);
+            let rate = if target == Vec3::ZERO {
+                settings.keyboard_deceleration
+            } else {
+                settings.keyboard_acceleration
+            };
+
+            move_towards(player_intent.movement, target, rate * time.delta_seconds())
+        }
+        MoveInput::Analog(stick) => stick.extend(0.0),
+    };
+
+    player_intent.activate_chainsaw = action_state.just_pressed(InputAction::ActivateChainsaw);
+}
+
+// Shield pushes the player away
+pub fn handle_chainsaw_deflected_event(
+    mut chainsaw_deflected_event_reader: EventReader<ChainsawDeflectedEvent>,
+    mut player_knockback: ResMut<PlayerKnockback>,
+) {
+    if let Some(deflected_event) = chainsaw_deflected_event_reader.iter().last() {
+        player_knockback.velocity = deflected_event.direction * PLAYER_DEFLECT_KNOCKBACK_SPEED;
+    }
+}
+
+// Moves the player according to the intent of the selected control scheme
 pub fn move_player(
     mut player_query: Query<(&mut Transform, &Player)>,
-    game_info: Res<GameInfo>,
+    player_intent: Res<PlayerIntent>,
+    mut player_knockback: ResMut<PlayerKnockback>,
     time: Res<Time>,
 ) {
     if let Ok((mut player_transform, player)) = player_query.get_single_mut() {
-        let cursor_position = game_info.cursor_position;
-        let destination = Vec3::new(cursor_position.x, cursor_position.y, 0.0);
-        let direction = (destination - player_transform.translation).normalize();
+        player_transform.translation +=
+            player_intent.movement * player.current_speed * time.delta_seconds();
 
-        if player_transform.translation.distance(destination) > 10.0 {
-            player_transform.translation += direction * player.current_speed * time.delta_seconds();
-        }
+        // Knockback fades out quickly
+        player_transform.translation += player_knockback.velocity * time.delta_seconds();
+        player_knockback.velocity *=
+            (1.0 - PLAYER_KNOCKBACK_DAMPING * time.delta_seconds()).max(0.0);
     }
 }
 
-pub fn update_player_progress(mut game_info: ResMut<GameInfo>, time: Res<Time>) {
-    if game_info.player_progress >= MAX_DEPTH {
+pub fn update_player_progress(
+    mut game_info: ResMut<GameInfo>,
+    mut player_info: ResMut<PlayerInfo>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    if game_info.player_progress >= MAX_DEPTH && settings.game_mode != GameMode::Endless {
         return;
     }
 
+    let previous_progress = game_info.player_progress;
     game_info.player_progress += PLAYER_FALLING_SPEED * time.delta_seconds();
+
+    // Every full meter of depth is worth the score
+    let meters_passed =
+        game_info.player_progress.floor() as usize - previous_progress.floor() as usize;
+    player_info.score += meters_passed * SCORE_PER_DEPTH;
     println!("{}", game_info.player_progress);
 }
 
 pub fn limit_player_movement(
     mut player_query: Query<(&mut Transform, &Player)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena_bounds: Res<ArenaBounds>,
 ) {
     if let Ok((mut player_transform, player_struct)) = player_query.get_single_mut() {
-        let primary_window = window_query.get_single().unwrap();
-        let x_max = primary_window.width() - player_struct.collider.size.x;
-        let x_min = 0.0 + player_struct.collider.size.x;
-        let y_max = primary_window.height() - player_struct.collider.size.y;
-        let y_min = 0.0 + player_struct.collider.size.y;
-
-        if player_transform.translation.x > x_max {
-            player_transform.translation.x = x_max;
-        }
-        if player_transform.translation.x < x_min {
-            player_transform.translation.x = x_min;
-        }
-        if player_transform.translation.y > y_max {
-            player_transform.translation.y = y_max;
-        }
-        if player_transform.translation.y < y_min {
-            player_transform.translation.y = y_min;
-        }
+        player_transform.translation = arena_bounds
+            .shrink(player_struct.collider.size)
+            .clamp(player_transform.translation);
     }
 }
 
@@ -329,10 +481,24 @@ pub fn limit_player_movement(
 pub fn handle_player_take_damage_event(
     mut player_take_damage_event_reader: EventReader<PlayerTakeDamageEvent>,
     mut game_over_event_writer: EventWriter<GameOverEvent>,
+    mut player_lost_health_event_writer: EventWriter<PlayerLostHealthEvent>,
     mut player_info: ResMut<PlayerInfo>,
+    mut combo: ResMut<Combo>,
+    mut active_pickup_effects: ResMut<ActivePickupEffects>,
     mut next_player_state: ResMut<NextState<PlayerState>>,
 ) {
-    for _ in player_take_damage_event_reader.iter() {
+    for player_take_damage_event in player_take_damage_event_reader.iter() {
+        // Shield takes the hit, the player still gets the invulnerability frames
+        if active_pickup_effects.consume(PickupKind::Shield) {
+            next_player_state.set(PlayerState::DAMAGED);
+            return;
+        }
+
+        combo.break_combo();
+        player_lost_health_event_writer.send(PlayerLostHealthEvent {
+            source: player_take_damage_event.source,
+        });
+
         if player_info.current_hp <= 1 {
             game_over_event_writer.send(GameOverEvent {});
             player_info.current_hp = 0;
@@ -377,3 +543,14 @@ pub fn tick_damage_invulnerability_timer(
 ) {
     damage_invulnerability_timer.timer.tick(time.delta());
 }
+
+// Moves `current` toward `target` by at most `max_step`
+fn move_towards(current: Vec3, target: Vec3, max_step: f32) -> Vec3 {
+    let delta = target - current;
+
+    if delta.length() <= max_step {
+        target
+    } else {
+        current + delta.normalize() * max_step
+    }
+}
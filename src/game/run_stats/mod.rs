@@ -0,0 +1,28 @@
+pub mod resources;
+mod systems;
+
+use super::player::systems::handle_player_take_damage_event;
+use super::GameState;
+use resources::*;
+use systems::*;
+
+use bevy::prelude::*;
+
+// Exported runs are written here, one file per run
+const RUN_STATS_DIRECTORY: &str = "runs";
+const BUILD_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+pub struct RunStatsPlugin;
+
+impl Plugin for RunStatsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RunStats>()
+            .add_system(reset_run_stats.in_schedule(OnExit(GameState::MainMenu)))
+            .add_system(
+                track_run_stats
+                    .after(handle_player_take_damage_event)
+                    .in_set(OnUpdate(GameState::Running)),
+            )
+            .add_system(export_run_stats.in_schedule(OnEnter(GameState::Gameover)));
+    }
+}
@@ -0,0 +1,54 @@
+use super::BUILD_VERSION;
+use crate::game::enemy::EnemyType;
+use crate::game::events::{DamageSource, PlayerDamageSource};
+use crate::game::player::PlayerState;
+use crate::settings::difficulty::Difficulty;
+use crate::settings::GameMode;
+
+use std::collections::BTreeMap;
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+// Everything measured during the run, exported as JSON for balancing
+#[derive(Resource, Debug, Default, Serialize)]
+pub struct RunStats {
+    // Seed of the RunRng, the same seed replays the same rolls
+    pub seed: u64,
+    pub version: String,
+    pub difficulty: Difficulty,
+    pub game_mode: GameMode,
+    // Seconds spent in the running game
+    pub duration: f32,
+    pub depth: f32,
+    pub score: usize,
+    pub highest_combo: usize,
+    pub is_boss_killed: bool,
+    pub boss_killed_by: Option<DamageSource>,
+    pub kills: BTreeMap<EnemyType, usize>,
+    pub damage_taken: BTreeMap<PlayerDamageSource, usize>,
+    pub fuel_collected: f32,
+    pub fuel_burned: f32,
+    // Seconds spent in every player state
+    pub time_in_state: BTreeMap<PlayerState, f32>,
+    pub chainsaw_activations: usize,
+    pub fire_waves: usize,
+    pub projectiles_reflected: usize,
+    pub pickups_missed: usize,
+}
+
+impl RunStats {
+    pub fn new(seed: u64, difficulty: Difficulty, game_mode: GameMode) -> Self {
+        RunStats {
+            seed,
+            version: BUILD_VERSION.to_string(),
+            difficulty,
+            game_mode,
+            ..default()
+        }
+    }
+
+    pub fn total_damage_taken(&self) -> usize {
+        self.damage_taken.values().sum()
+    }
+}
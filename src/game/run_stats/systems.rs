@@ -0,0 +1,87 @@
+use super::resources::RunStats;
+use super::RUN_STATS_DIRECTORY;
+use crate::game::combo::resources::Combo;
+use crate::game::enemy::EnemyType;
+use crate::game::events::{ChainsawFireWave, EnemyKilledEvent, PlayerLostHealthEvent};
+use crate::game::player::resources::PlayerInfo;
+use crate::game::player::PlayerState;
+use crate::game::{GameInfo, RunRng};
+use crate::settings::resources::GameSettings;
+
+use bevy::prelude::*;
+use rand::prelude::*;
+
+// Every run gets a new seed, the exported stats keep it
+pub fn reset_run_stats(
+    mut run_stats: ResMut<RunStats>,
+    mut run_rng: ResMut<RunRng>,
+    settings: Res<GameSettings>,
+) {
+    *run_rng = RunRng::new(thread_rng().gen());
+    *run_stats = RunStats::new(run_rng.seed(), settings.difficulty, settings.game_mode);
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn track_run_stats(
+    mut player_lost_health_event_reader: EventReader<PlayerLostHealthEvent>,
+    mut fire_wave_event_reader: EventReader<ChainsawFireWave>,
+    mut enemy_killed_event_reader: EventReader<EnemyKilledEvent>,
+    mut run_stats: ResMut<RunStats>,
+    player_state: Res<State<PlayerState>>,
+    player_info: Res<PlayerInfo>,
+    game_info: Res<GameInfo>,
+    combo: Res<Combo>,
+    time: Res<Time>,
+) {
+    for player_lost_health_event in player_lost_health_event_reader.iter() {
+        *run_stats
+            .damage_taken
+            .entry(player_lost_health_event.source)
+            .or_default() += 1;
+    }
+
+    run_stats.fire_waves += fire_wave_event_reader.iter().count();
+
+    for enemy_killed_event in enemy_killed_event_reader.iter() {
+        *run_stats
+            .kills
+            .entry(enemy_killed_event.enemy_type)
+            .or_default() += 1;
+
+        if enemy_killed_event.enemy_type == EnemyType::Boss {
+            run_stats.boss_killed_by = Some(enemy_killed_event.source);
+            run_stats.is_boss_killed = true;
+        }
+    }
+
+    *run_stats.time_in_state.entry(player_state.0).or_default() += time.delta_seconds();
+    run_stats.duration += time.delta_seconds();
+    run_stats.depth = game_info.player_progress;
+    run_stats.score = player_info.score;
+    run_stats.highest_combo = combo.highest;
+}
+
+// The run ends only with the game over, in the endless mode it goes on past the boss
+pub fn export_run_stats(run_stats: Res<RunStats>) {
+    write_run_stats(&run_stats);
+}
+
+fn write_run_stats(run_stats: &RunStats) {
+    let contents = match serde_json::to_string_pretty(run_stats) {
+        Ok(contents) => contents,
+        Err(error) => {
+            println!("Failed to serialize the run stats: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::create_dir_all(RUN_STATS_DIRECTORY) {
+        println!("Failed to create {}: {}", RUN_STATS_DIRECTORY, error);
+        return;
+    }
+
+    let file_path = format!("{}/run_{:016x}.json", RUN_STATS_DIRECTORY, run_stats.seed);
+    if let Err(error) = std::fs::write(&file_path, contents) {
+        println!("Failed to save {}: {}", file_path, error);
+    }
+}
@@ -0,0 +1,109 @@
+use super::{StackingRule, StatusEffectKind};
+
+use bevy::prelude::*;
+use std::time::Duration;
+
+pub struct StatusEffect {
+    pub kind: StatusEffectKind,
+    pub timer: Timer,
+    pub stacks: u32,
+    // How many times the effect has spread from enemy to enemy
+    pub chain_depth: u32,
+}
+
+// Timed effects on the entity. Movement, AI and damage systems ask it for the modifiers.
+#[derive(Component, Default)]
+pub struct StatusEffects {
+    // The last applied effect is shown by the tint
+    pub effects: Vec<StatusEffect>,
+    // Effect, whose tint the sprite has now
+    pub tinted_kind: Option<StatusEffectKind>,
+}
+
+impl StatusEffects {
+    pub fn apply(&mut self, kind: StatusEffectKind) {
+        self.apply_chained(kind, 0);
+    }
+
+    // Applies the effect spread from another enemy
+    pub fn apply_chained(&mut self, kind: StatusEffectKind, chain_depth: u32) {
+        self.apply_with(kind, kind.duration(), chain_depth);
+    }
+
+    // Applies the effect for the given time instead of its own duration
+    pub fn apply_for(&mut self, kind: StatusEffectKind, duration: f32) {
+        self.apply_with(kind, duration, 0);
+    }
+
+    // Running effect keeps the longer of the remaining and the new duration
+    fn apply_with(&mut self, kind: StatusEffectKind, duration: f32, chain_depth: u32) {
+        let Some(index) = self.effects.iter().position(|effect| effect.kind == kind) else {
+            self.effects.push(StatusEffect {
+                kind,
+                timer: Timer::from_seconds(duration, TimerMode::Once),
+                stacks: 1,
+                chain_depth,
+            });
+            return;
+        };
+
+        let effect = &mut self.effects[index];
+        match kind.stacking() {
+            StackingRule::Ignore => return,
+            StackingRule::Refresh => {}
+            StackingRule::Stack(max_stacks) => {
+                effect.stacks = (effect.stacks + 1).min(max_stacks);
+            }
+        }
+        effect.chain_depth = effect.chain_depth.min(chain_depth);
+
+        if effect.timer.remaining_secs() >= duration {
+            return;
+        }
+
+        let mut effect = self.effects.remove(index);
+        effect.timer.set_duration(Duration::from_secs_f32(duration));
+        effect.timer.reset();
+        self.effects.push(effect);
+    }
+
+    pub fn has(&self, kind: StatusEffectKind) -> bool {
+        self.effects.iter().any(|effect| effect.kind == kind)
+    }
+
+    pub fn get(&self, kind: StatusEffectKind) -> Option<&StatusEffect> {
+        self.effects.iter().find(|effect| effect.kind == kind)
+    }
+
+    // Applies bonuses and multipliers of all effects to the speed
+    pub fn modify_speed(&self, speed: f32) -> f32 {
+        let speed_bonus: f32 = self
+            .effects
+            .iter()
+            .map(|effect| effect.kind.speed_bonus())
+            .sum();
+        let speed_multiplier: f32 = self
+            .effects
+            .iter()
+            .map(|effect| effect.kind.speed_multiplier())
+            .product();
+
+        (speed + speed_bonus) * speed_multiplier
+    }
+
+    pub fn damage_taken_multiplier(&self) -> f32 {
+        self.effects
+            .iter()
+            .map(|effect| effect.kind.damage_taken_multiplier())
+            .product()
+    }
+
+    pub fn disables_ai(&self) -> bool {
+        self.effects.iter().any(|effect| effect.kind.disables_ai())
+    }
+
+    // Burning enemies run in a straight line and bounce off the arena edges
+    pub fn is_panicking(&self) -> bool {
+        self.has(StatusEffectKind::Burning)
+    }
+}
@@ -0,0 +1,158 @@
+pub mod components;
+mod systems;
+
+use super::GameState;
+use systems::*;
+
+use bevy::prelude::*;
+use serde::Serialize;
+
+// === Burning ===
+const BURNING_DURATION: f32 = 4.0;
+const BURNING_DAMAGE_PER_SECOND: f32 = 2.0;
+const BURNING_SPEED_GAIN: f32 = 50.0;
+// Periodical orange flash
+const BURNING_FLASH_SPEED: f32 = 6.0;
+const BURNING_FLASH_GREEN_MIN: f32 = 100.0 / 255.0;
+const BURNING_FLASH_GREEN_MAX: f32 = 200.0 / 255.0;
+
+// === Slowed ===
+const SLOWED_DURATION: f32 = 2.0;
+const SLOWED_SPEED_MULTIPLIER: f32 = 0.5;
+const SLOWED_TINT: Color = Color::rgb(0.6, 0.7, 1.0);
+
+// === Stunned ===
+const STUNNED_DURATION: f32 = 1.5;
+const STUNNED_TINT: Color = Color::rgb(0.55, 0.55, 0.55);
+
+// === Frozen ===
+const FROZEN_DURATION: f32 = 2.5;
+const FROZEN_DAMAGE_TAKEN_MULTIPLIER: f32 = 1.5;
+const FROZEN_TINT: Color = Color::rgb(0.6, 0.95, 1.0);
+
+// === Bleeding ===
+const BLEEDING_DURATION: f32 = 5.0;
+// Per stack
+const BLEEDING_DAMAGE_PER_SECOND: f32 = 1.5;
+const BLEEDING_MAX_STACKS: u32 = 5;
+const BLEEDING_TINT: Color = Color::rgb(0.8, 0.3, 0.3);
+
+pub struct StatusEffectsPlugin;
+
+impl Plugin for StatusEffectsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                tick_status_effects,
+                spread_burning.after(tick_status_effects),
+                tint_status_effects.after(spread_burning),
+            )
+                .in_set(OnUpdate(GameState::Running)),
+        );
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub enum StatusEffectKind {
+    // Damage over time, enemy panics and runs around faster
+    Burning,
+    Slowed,
+    // Can't move or attack
+    Stunned,
+    // Can't move or attack and takes more damage
+    Frozen,
+    // Damage over time, which stacks
+    Bleeding,
+}
+
+// What happens, when the effect is applied again while it's active
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum StackingRule {
+    // Restart the duration, unless more of it remains
+    Refresh,
+    // Keep the running effect as is
+    Ignore,
+    // Add a stack (up to the max) and restart the duration like Refresh
+    Stack(u32),
+}
+
+impl StatusEffectKind {
+    pub fn duration(&self) -> f32 {
+        match self {
+            StatusEffectKind::Burning => BURNING_DURATION,
+            StatusEffectKind::Slowed => SLOWED_DURATION,
+            StatusEffectKind::Stunned => STUNNED_DURATION,
+            StatusEffectKind::Frozen => FROZEN_DURATION,
+            StatusEffectKind::Bleeding => BLEEDING_DURATION,
+        }
+    }
+
+    pub fn stacking(&self) -> StackingRule {
+        match self {
+            StatusEffectKind::Stunned => StackingRule::Ignore,
+            StatusEffectKind::Bleeding => StackingRule::Stack(BLEEDING_MAX_STACKS),
+            _ => StackingRule::Refresh,
+        }
+    }
+
+    // Damage per second of a single stack
+    pub fn damage_per_second(&self) -> f32 {
+        match self {
+            StatusEffectKind::Burning => BURNING_DAMAGE_PER_SECOND,
+            StatusEffectKind::Bleeding => BLEEDING_DAMAGE_PER_SECOND,
+            _ => 0.0,
+        }
+    }
+
+    pub fn speed_multiplier(&self) -> f32 {
+        match self {
+            StatusEffectKind::Slowed => SLOWED_SPEED_MULTIPLIER,
+            StatusEffectKind::Stunned | StatusEffectKind::Frozen => 0.0,
+            _ => 1.0,
+        }
+    }
+
+    // Added to the speed before the multipliers
+    pub fn speed_bonus(&self) -> f32 {
+        match self {
+            StatusEffectKind::Burning => BURNING_SPEED_GAIN,
+            _ => 0.0,
+        }
+    }
+
+    pub fn damage_taken_multiplier(&self) -> f32 {
+        match self {
+            StatusEffectKind::Frozen => FROZEN_DAMAGE_TAKEN_MULTIPLIER,
+            _ => 1.0,
+        }
+    }
+
+    pub fn disables_ai(&self) -> bool {
+        matches!(
+            self,
+            StatusEffectKind::Burning | StatusEffectKind::Stunned | StatusEffectKind::Frozen
+        )
+    }
+
+    // Tint changes every frame, not only when the effect is applied
+    pub fn is_tint_animated(&self) -> bool {
+        matches!(self, StatusEffectKind::Burning)
+    }
+
+    // Elapsed is the time since the effect was applied
+    pub fn tint(&self, elapsed: f32) -> Color {
+        match self {
+            StatusEffectKind::Burning => {
+                let flash = 0.5 + 0.5 * (elapsed * BURNING_FLASH_SPEED).cos();
+                let green = BURNING_FLASH_GREEN_MIN
+                    + (BURNING_FLASH_GREEN_MAX - BURNING_FLASH_GREEN_MIN) * flash;
+
+                Color::rgb(1.0, green, green)
+            }
+            StatusEffectKind::Slowed => SLOWED_TINT,
+            StatusEffectKind::Stunned => STUNNED_TINT,
+            StatusEffectKind::Frozen => FROZEN_TINT,
+            StatusEffectKind::Bleeding => BLEEDING_TINT,
+        }
+    }
+}
@@ -0,0 +1,121 @@
+use super::components::StatusEffects;
+use super::StatusEffectKind;
+use crate::game::enemy::components::{Enemy, EnemyState};
+use crate::game::events::{DamageSource, EnemyTakeDamageEvent};
+use crate::game::RunRng;
+use crate::settings::resources::GameSettings;
+
+use bevy::prelude::*;
+use bevy::sprite::collide_aabb::collide;
+use rand::prelude::*;
+
+// Deals the damage over time and removes the expired effects
+pub fn tick_status_effects(
+    mut enemy_take_damage_event_writer: EventWriter<EnemyTakeDamageEvent>,
+    mut status_effects_query: Query<(Entity, &mut StatusEffects)>,
+    time: Res<Time>,
+) {
+    for (entity, mut status_effects) in status_effects_query.iter_mut() {
+        for effect in status_effects.effects.iter_mut() {
+            effect.timer.tick(time.delta());
+
+            let damage =
+                effect.kind.damage_per_second() * effect.stacks as f32 * time.delta_seconds();
+
+            if damage > 0.0 {
+                enemy_take_damage_event_writer.send(EnemyTakeDamageEvent {
+                    enemy_entity: entity,
+                    damage,
+                    source: DamageSource::StatusEffect(effect.kind),
+                    status_effect: None,
+                    is_critical: false,
+                });
+            }
+        }
+
+        status_effects
+            .effects
+            .retain(|effect| !effect.timer.finished());
+    }
+}
+
+// Burning enemies can ignite the enemies they touch, until the chain gets too long.
+// Both the chance and the chain length depend on the difficulty.
+pub fn spread_burning(
+    mut enemies_query: Query<(Entity, &Transform, &Enemy, &mut StatusEffects)>,
+    mut run_rng: ResMut<RunRng>,
+    settings: Res<GameSettings>,
+    time: Res<Time>,
+) {
+    let difficulty_profile = settings.difficulty.profile();
+
+    let fire_sources: Vec<(Entity, Vec3, Vec2, u32)> = enemies_query
+        .iter()
+        .filter_map(|(entity, transform, enemy_struct, status_effects)| {
+            let burning = status_effects.get(StatusEffectKind::Burning)?;
+
+            (burning.chain_depth < difficulty_profile.burning_spread_max_chain_depth).then_some((
+                entity,
+                transform.translation,
+                enemy_struct.collider.size,
+                burning.chain_depth,
+            ))
+        })
+        .collect();
+
+    if fire_sources.is_empty() {
+        return;
+    }
+
+    let spread_chance = difficulty_profile.burning_spread_chance_per_second * time.delta_seconds();
+
+    for (entity, transform, enemy_struct, mut status_effects) in enemies_query.iter_mut() {
+        if enemy_struct.state != EnemyState::Engaging
+            || status_effects.has(StatusEffectKind::Burning)
+        {
+            continue;
+        }
+
+        for &(source_entity, source_position, source_size, chain_depth) in fire_sources.iter() {
+            if source_entity == entity {
+                continue;
+            }
+
+            let touching = collide(
+                transform.translation,
+                enemy_struct.collider.size,
+                source_position,
+                source_size,
+            )
+            .is_some();
+
+            if touching && run_rng.gen::<f32>() < spread_chance {
+                status_effects.apply_chained(StatusEffectKind::Burning, chain_depth + 1);
+                break;
+            }
+        }
+    }
+}
+
+// Tints the sprite with the last applied effect. Alpha is left to the other systems.
+// Sprite is only touched, when the shown effect changes or its tint is animated.
+pub fn tint_status_effects(mut status_effects_query: Query<(&mut StatusEffects, &mut Sprite)>) {
+    for (mut status_effects, mut sprite) in status_effects_query.iter_mut() {
+        let shown_kind = status_effects.effects.last().map(|effect| effect.kind);
+        let is_animated = shown_kind.is_some_and(|kind| kind.is_tint_animated());
+
+        if shown_kind == status_effects.tinted_kind && !is_animated {
+            continue;
+        }
+        status_effects.tinted_kind = shown_kind;
+
+        let tint = status_effects
+            .effects
+            .last()
+            .map_or(Color::WHITE, |effect| {
+                effect.kind.tint(effect.timer.elapsed_secs())
+            });
+
+        sprite.color = tint.with_a(sprite.color.a());
+    }
+}
@@ -1,96 +1,120 @@
+use super::biomes::resources::BiomeState;
+use super::biomes::BIOMES;
 use super::components::*;
+use super::enemy::components::Enemy;
 use super::events::*;
+use super::pickups::resources::{ActivePickupEffects, PickupPity};
+use super::pickups::{pickup_spawn_rate_multiplier, PickupDefinition, PICKUPS};
 use super::player::components::Player;
-use super::player::PLAYER_CHAINSAW_COLLIDER_SIZE;
-use super::BACKGROUND_LIGHTNESS;
-use super::HEALTH_SPAWN_CHANCE;
-use super::{GameInfo, GameState, PickupSpawnTimer};
-use super::{
-    FUEL_PICKUP_COLLIDER_SIZE, FUEL_PICKUP_SPRITE_SIZE, HEALTH_PICKUP_COLLIDER_SIZE,
-    PARALLAX_SPEED, PICKUP_SPEED,
-};
+use super::player::resources::PlayerInfo;
+use super::player::{PLAYER_CHAINSAW_COLLIDER_SIZE, PLAYER_MAX_HEALTH};
+use super::run_stats::resources::RunStats;
+use super::{ArenaBounds, Faction, GameInfo, GameState, PickupSpawnTimer, RunRng, ARENA_SIZE};
+use super::{BONUS_PICKUP_SPAWN_CHANCE, PARALLAX_SPEED, PICKUP_SPEED};
+use super::{LASER_CHARGE_COLOR, LASER_CHARGE_WIDTH, LASER_FIRE_COLOR};
+use super::{MAX_ENEMIES_NUM, PICKUP_SPAWN_PERIOD};
+use super::{PICKUP_ENEMY_CLEARANCE, PICKUP_SPAWN_ATTEMPTS};
+use crate::controls::resources::{ActionState, TouchControls};
+use crate::controls::InputAction;
+use crate::settings::resources::GameSettings;
 
 use bevy::prelude::*;
+use bevy::render::camera::ScalingMode;
 use bevy::window::PrimaryWindow;
 use rand::prelude::*;
+use std::time::Duration;
 
+// Every biome has its own pair of layers, only the current one is visible
 pub fn spawn_parallax_background(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    arena_bounds: Res<ArenaBounds>,
     asset_server: Res<AssetServer>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
+    let bg_size = ARENA_SIZE;
 
-    let bg_size = Vec2::new(260.0 * 2.0, 320.0 * 2.0);
+    for (biome_index, biome) in BIOMES.iter().enumerate() {
+        let (alpha, visibility) = if biome_index == 0 {
+            (1.0, Visibility::Inherited)
+        } else {
+            (0.0, Visibility::Hidden)
+        };
 
-    // Top background. Spawn it at the center of the screen.
-    commands.spawn((
-        SpriteBundle {
-            transform: Transform::from_xyz(
-                primary_window.width() / 2.0,
-                primary_window.height() / 2.0,
-                -1.0,
-            ),
-            texture: asset_server.load("sprites/bg1.png"),
-            sprite: Sprite {
-                color: Color::Hsla {
-                    hue: 360.,
-                    saturation: 0.,
-                    lightness: BACKGROUND_LIGHTNESS,
-                    alpha: 1.,
+        // Top background at the center of the arena, bottom one right below the arena
+        for (layer_index, layer) in biome.background_layers.iter().enumerate() {
+            commands.spawn((
+                SpriteBundle {
+                    transform: Transform::from_xyz(
+                        arena_bounds.center().x,
+                        arena_bounds.center().y - arena_bounds.height() * layer_index as f32,
+                        -1.0,
+                    ),
+                    texture: asset_server.load(*layer),
+                    sprite: Sprite {
+                        color: biome.palette.with_a(alpha),
+                        custom_size: Some(bg_size),
+                        ..default()
+                    },
+                    visibility,
+                    ..default()
                 },
-                custom_size: Some(bg_size),
-                ..default()
-            },
-            ..default()
-        },
-        ParallaxBackground { size: bg_size },
-    ));
-
-    // Bottom background. Spawn it outside of the screen.
-    commands.spawn((
-        SpriteBundle {
-            transform: Transform::from_xyz(
-                primary_window.width() / 2.0,
-                -(primary_window.height() / 2.0),
-                -1.0,
-            ),
-            texture: asset_server.load("sprites/bg2.png"),
-            sprite: Sprite {
-                color: Color::Hsla {
-                    hue: 360.,
-                    saturation: 0.,
-                    lightness: BACKGROUND_LIGHTNESS,
-                    alpha: 1.,
+                ParallaxBackground {
+                    size: bg_size,
+                    biome: biome_index,
                 },
-                custom_size: Some(bg_size),
-                ..default()
-            },
-            ..default()
-        },
-        ParallaxBackground { size: bg_size },
-    ));
+            ));
+        }
+    }
 }
 
 // Moves background up
 // When goes out of the bounds -> move it at the bottom of the screen and move it for one additional frame more
+// New biome fades in over the previous one, which stays opaque below until the crossfade ends
 // TODO: fix overheads
 pub fn move_parallax_background(
-    mut parallax_background_query: Query<(&mut Transform, &ParallaxBackground)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut parallax_background_query: Query<(
+        &mut Transform,
+        &mut Sprite,
+        &mut Visibility,
+        &ParallaxBackground,
+    )>,
+    arena_bounds: Res<ArenaBounds>,
+    biome_state: Res<BiomeState>,
     time: Res<Time>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
     let parallax_direction = Vec3::new(0.0, 1.0, 0.0).normalize();
 
-    for (mut parallax_bg_transform, _) in parallax_background_query.iter_mut() {
+    for (
+        mut parallax_bg_transform,
+        mut parallax_bg_sprite,
+        mut parallax_bg_visibility,
+        parallax_bg,
+    ) in parallax_background_query.iter_mut()
+    {
         parallax_bg_transform.translation +=
             parallax_direction * PARALLAX_SPEED * time.delta_seconds();
 
-        if parallax_bg_transform.translation.y - primary_window.height() / 2.0
-            > primary_window.height()
-        {
-            parallax_bg_transform.translation.y -= 2.0 * primary_window.height();
+        if parallax_bg_transform.translation.y - arena_bounds.height() / 2.0 > arena_bounds.max.y {
+            parallax_bg_transform.translation.y -= 2.0 * arena_bounds.height();
+        }
+
+        let (alpha, z) = if parallax_bg.biome == biome_state.current {
+            (biome_state.weight(parallax_bg.biome), -1.0)
+        } else if parallax_bg.biome == biome_state.previous && biome_state.is_crossfading() {
+            (1.0, -1.1)
+        } else {
+            (0.0, -1.1)
+        };
+
+        parallax_bg_sprite.color.set_a(alpha);
+        parallax_bg_transform.translation.z = z;
+
+        let visibility = if alpha > 0.0 {
+            Visibility::Inherited
+        } else {
+            Visibility::Hidden
+        };
+        if *parallax_bg_visibility != visibility {
+            *parallax_bg_visibility = visibility;
         }
     }
 }
@@ -102,133 +126,271 @@ pub fn play_music(audio: Res<Audio>, asset_server: Res<AssetServer>) {
 
 pub fn handle_projectiles(
     mut commands: Commands,
-    mut projectiles_query: Query<(Entity, &mut Transform, &Projectile)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut projectiles_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut Projectile,
+        Option<&mut Bouncing>,
+    )>,
+    arena_bounds: Res<ArenaBounds>,
+    active_pickup_effects: Res<ActivePickupEffects>,
     time: Res<Time>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
+    for (entity, mut projectile_transform, mut projectile_struct, bouncing) in
+        projectiles_query.iter_mut()
+    {
+        let projectile_bounds = arena_bounds.shrink(projectile_struct.collider.size);
+
+        let speed_multiplier = match projectile_struct.faction {
+            Faction::Enemy => active_pickup_effects.projectile_speed_multiplier(),
+            _ => 1.0,
+        };
+        projectile_transform.translation += projectile_struct.direction
+            * projectile_struct.speed
+            * speed_multiplier
+            * time.delta_seconds();
+
+        if projectile_struct.lifetime.tick(time.delta()).finished() {
+            commands.entity(entity).despawn();
+            continue;
+        }
+
+        if projectile_bounds.contains(projectile_transform.translation) {
+            continue;
+        }
+
+        match bouncing {
+            Some(mut bouncing) if bouncing.bounces_left > 0 => {
+                let translation = projectile_transform.translation;
+
+                if translation.x < projectile_bounds.min.x
+                    || translation.x > projectile_bounds.max.x
+                {
+                    projectile_struct.direction.x *= -1.0;
+                }
+                if translation.y < projectile_bounds.min.y
+                    || translation.y > projectile_bounds.max.y
+                {
+                    projectile_struct.direction.y *= -1.0;
+                }
+
+                projectile_transform.translation = projectile_bounds.clamp(translation);
+                bouncing.bounces_left -= 1;
+            }
+            _ => commands.entity(entity).despawn(),
+        }
+    }
+}
+
+pub fn accelerate_projectiles(
+    mut projectiles_query: Query<(&mut Projectile, &mut ProjectileSpeedCurve)>,
+    time: Res<Time>,
+) {
+    for (mut projectile_struct, mut speed_curve) in projectiles_query.iter_mut() {
+        speed_curve.timer.tick(time.delta());
+        projectile_struct.speed =
+            speed_curve.start + (speed_curve.end - speed_curve.start) * speed_curve.timer.percent();
+    }
+}
+
+// Homing projectiles turn to the player, but not faster than their turn rate
+pub fn steer_homing_projectiles(
+    mut projectiles_query: Query<(&Transform, &mut Projectile, &Homing)>,
+    player_query: Query<&Transform, With<Player>>,
+    time: Res<Time>,
+) {
+    let Ok(player_transform) = player_query.get_single() else {
+        return;
+    };
+
+    for (projectile_transform, mut projectile_struct, homing) in projectiles_query.iter_mut() {
+        let to_player =
+            (player_transform.translation - projectile_transform.translation).truncate();
+        let direction = projectile_struct.direction.truncate();
+
+        if to_player == Vec2::ZERO || direction == Vec2::ZERO {
+            continue;
+        }
+
+        let max_turn = homing.turn_rate * time.delta_seconds();
+        let turn = direction
+            .angle_between(to_player)
+            .clamp(-max_turn, max_turn);
+
+        projectile_struct.direction = Vec2::from_angle(turn).rotate(direction).extend(0.0);
+    }
+}
+
+// Moves projectiles sideways, so they follow a sine wave around their path
+pub fn apply_projectile_sine_motion(
+    mut projectiles_query: Query<(&mut Transform, &Projectile, &mut SineMotion)>,
+    time: Res<Time>,
+) {
+    for (mut projectile_transform, projectile_struct, mut sine_motion) in
+        projectiles_query.iter_mut()
+    {
+        sine_motion.elapsed += time.delta_seconds();
+
+        let offset = sine_motion.amplitude
+            * (sine_motion.elapsed * sine_motion.frequency * std::f32::consts::TAU).sin();
+        let sideways = projectile_struct.direction.truncate().perp().extend(0.0);
 
-    for (entity, mut projectile_transform, projectile_struct) in projectiles_query.iter_mut() {
-        let min_x = 0.0 + projectile_struct.collider.size.x;
-        let max_x = primary_window.width() - projectile_struct.collider.size.x;
-        let min_y = 0.0 + projectile_struct.collider.size.y;
-        let max_y = primary_window.height() - projectile_struct.collider.size.y;
+        projectile_transform.translation += sideways * (offset - sine_motion.offset);
+        sine_motion.offset = offset;
+    }
+}
+
+// Lasers show a thin warning line while charging, then fire a full beam for a while
+pub fn handle_lasers(
+    mut commands: Commands,
+    mut lasers_query: Query<(Entity, &mut Laser, &mut Sprite)>,
+    time: Res<Time>,
+) {
+    for (entity, mut laser, mut laser_sprite) in lasers_query.iter_mut() {
+        if !laser.is_firing() {
+            laser.charge_timer.tick(time.delta());
 
-        projectile_transform.translation +=
-            projectile_struct.direction * projectile_struct.speed * time.delta_seconds();
+            // Warning gets brighter before the shot
+            laser_sprite.color = LASER_CHARGE_COLOR
+                .with_a(LASER_CHARGE_COLOR.a() * (0.3 + 0.7 * laser.charge_timer.percent()));
+            laser_sprite.custom_size = Some(Vec2::new(laser.length, LASER_CHARGE_WIDTH));
+            continue;
+        }
 
-        if projectile_transform.translation.x < min_x
-            || projectile_transform.translation.x > max_x
-            || projectile_transform.translation.y < min_y
-            || projectile_transform.translation.y > max_y
-        {
+        if laser.fire_timer.tick(time.delta()).finished() {
             commands.entity(entity).despawn();
+            continue;
         }
+
+        laser_sprite.color = LASER_FIRE_COLOR;
+        laser_sprite.custom_size = Some(Vec2::new(laser.length, laser.width));
     }
 }
 
-pub fn spawn_camera(mut commands: Commands, window_query: Query<&Window, With<PrimaryWindow>>) {
-    let primary_window = window_query.get_single().unwrap();
+// Camera always shows the whole arena. When the window has a different aspect ratio,
+// the rest is filled with the clear color (letterboxing).
+pub fn spawn_camera(mut commands: Commands, arena_bounds: Res<ArenaBounds>) {
+    let mut camera_bundle = Camera2dBundle {
+        transform: Transform::from_translation(arena_bounds.center().extend(5.0)),
+        ..default()
+    };
+    camera_bundle.projection.scaling_mode = ScalingMode::AutoMin {
+        min_width: arena_bounds.width(),
+        min_height: arena_bounds.height(),
+    };
 
-    // commands.spawn((
-    //     Camera2dBundle {
-    //         transform: Transform::from_xyz(
-    //             primary_window.width() / 2.0,
-    //             primary_window.height() / 2.0,
-    //             0.0,
-    //         ),
-    //         ..default()
-    //     },
-    //     MainCamera {},
-    // ));
-
-    commands.spawn({
-        let bundle = (
-            Camera2dBundle {
-                transform: Transform::from_xyz(
-                    primary_window.width() / 2.0,
-                    primary_window.height() / 2.0,
-                    5.0,
-                ),
-                ..default()
-            },
-            MainCamera {},
-        );
-        // bundle.0.projection.scaling_mode =
-        //     bevy::render::camera::ScalingMode::FixedHorizontal(260.0);
-        // bundle.0.projection.scaling_mode = bevy::render::camera::ScalingMode::WindowSize(1.0);
-        // bundle.0.transform.scale = Vec3::new(1., 1., 1.);
-        bundle
-    });
+    commands.spawn((camera_bundle, MainCamera {}));
 }
 
-// Function that spawns randowm pickups over time at the y = 0, and random x.
+// Spawns pickups from the spawn table of the current biome below the bottom edge
+#[allow(clippy::too_many_arguments)]
 pub fn spawn_pickups_over_time(
     mut commands: Commands,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut pickup_pity: ResMut<PickupPity>,
+    arena_bounds: Res<ArenaBounds>,
     asset_server: Res<AssetServer>,
     pickup_timer: Res<PickupSpawnTimer>,
+    biome_state: Res<BiomeState>,
+    player_info: Res<PlayerInfo>,
+    enemies_query: Query<(&Transform, &Enemy)>,
+    mut run_rng: ResMut<RunRng>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
+    if !pickup_timer.timer.just_finished() {
+        return;
+    }
 
-    if pickup_timer.timer.just_finished() {
-        spawn_fuel_bundle(&mut commands, &asset_server, primary_window);
+    let rng = &mut *run_rng;
+    let pickups_num = if rng.gen::<f32>() < BONUS_PICKUP_SPAWN_CHANCE {
+        2
+    } else {
+        1
+    };
 
-        if random::<f32>() > HEALTH_SPAWN_CHANCE {
-            spawn_health_bundle(&mut commands, &asset_server, primary_window);
+    pickup_pity.tick();
+    for _ in 0..pickups_num {
+        // Pity pickup goes first, so the player doesn't go without fuel or health for too long
+        let pickup = match pickup_pity.due(&player_info) {
+            Some(pickup) => pickup,
+            // Spawn weights and biome multipliers are positive constants, so the choice can't fail
+            None => PICKUPS
+                .choose_weighted(rng, |pickup| {
+                    pickup.spawn_weight_in(biome_state.biome(), &player_info)
+                })
+                .expect("pickup spawn weights must be positive"),
+        };
+
+        let spawn_position = Vec3::new(
+            find_pickup_spawn_x(&arena_bounds, pickup.collider_size, &enemies_query, rng),
+            arena_bounds.min.y - PLAYER_CHAINSAW_COLLIDER_SIZE.y,
+            0.0,
+        );
+        spawn_pickup(&mut commands, &asset_server, pickup, spawn_position);
+        pickup_pity.reset(pickup.kind);
+    }
+}
+
+// Tries a few random positions and keeps the first one, which isn't in the way of an enemy
+fn find_pickup_spawn_x(
+    arena_bounds: &ArenaBounds,
+    size: Vec2,
+    enemies_query: &Query<(&Transform, &Enemy)>,
+    rng: &mut RunRng,
+) -> f32 {
+    let spawn_y = arena_bounds.min.y - PLAYER_CHAINSAW_COLLIDER_SIZE.y;
+    // Horizontal gap to the nearest enemy near the bottom edge, negative when overlapping
+    let gap_to_enemies = |x: f32| {
+        enemies_query
+            .iter()
+            .filter(|(enemy_transform, _)| {
+                (enemy_transform.translation.y - spawn_y).abs() < PICKUP_ENEMY_CLEARANCE
+            })
+            .map(|(enemy_transform, enemy_struct)| {
+                (enemy_transform.translation.x - x).abs()
+                    - (size.x + enemy_struct.collider.size.x) / 2.0
+            })
+            .fold(f32::MAX, f32::min)
+    };
+
+    let mut best_x = arena_bounds.random_x(size, rng);
+    let mut best_gap = gap_to_enemies(best_x);
+    for _ in 1..PICKUP_SPAWN_ATTEMPTS {
+        if best_gap >= 0.0 {
+            break;
+        }
+
+        let x = arena_bounds.random_x(size, rng);
+        let gap = gap_to_enemies(x);
+        if gap > best_gap {
+            best_x = x;
+            best_gap = gap;
         }
     }
+
+    best_x
 }
 
-pub fn spawn_fuel_bundle(
+pub fn spawn_pickup(
     commands: &mut Commands,
     asset_server: &Res<AssetServer>,
-    primary_window: &Window,
+    pickup: &PickupDefinition,
+    position: Vec3,
 ) {
     commands.spawn((
         SpriteBundle {
-            transform: Transform::from_xyz(
-                random::<f32>() * (primary_window.width() - PLAYER_CHAINSAW_COLLIDER_SIZE.x),
-                0.0 - PLAYER_CHAINSAW_COLLIDER_SIZE.y,
-                0.0,
-            ),
-            texture: asset_server.load("sprites/fuel.png"),
+            transform: Transform::from_translation(position),
+            texture: asset_server.load(pickup.sprite),
             sprite: Sprite {
-                custom_size: Some(FUEL_PICKUP_SPRITE_SIZE),
+                custom_size: pickup.sprite_size,
                 ..default()
             },
             ..default()
         },
         Pickup {
             collider: Collider {
-                size: FUEL_PICKUP_COLLIDER_SIZE,
+                size: pickup.collider_size,
             },
+            kind: pickup.kind,
         },
-        FuelPickup {},
-    ));
-}
-
-pub fn spawn_health_bundle(
-    commands: &mut Commands,
-    asset_server: &Res<AssetServer>,
-    primary_window: &Window,
-) {
-    commands.spawn((
-        SpriteBundle {
-            transform: Transform::from_xyz(
-                random::<f32>() * (primary_window.width() - PLAYER_CHAINSAW_COLLIDER_SIZE.x),
-                0.0 - PLAYER_CHAINSAW_COLLIDER_SIZE.y,
-                0.0,
-            ),
-            texture: asset_server.load("sprites/health.png"),
-            ..default()
-        },
-        Pickup {
-            collider: Collider {
-                size: HEALTH_PICKUP_COLLIDER_SIZE,
-            },
-        },
-        HealthPickup {},
     ));
 }
 
@@ -246,17 +408,37 @@ pub fn move_pickups_vertically(
     }
 }
 
+// Scales the game constants for the chosen difficulty, when the run starts
+pub fn apply_difficulty(
+    mut game_info: ResMut<GameInfo>,
+    mut player_info: ResMut<PlayerInfo>,
+    mut pickup_spawn_timer: ResMut<PickupSpawnTimer>,
+    settings: Res<GameSettings>,
+) {
+    let difficulty_profile = settings.difficulty.profile();
+
+    game_info.max_enemies_num = difficulty_profile.max_enemies_num(MAX_ENEMIES_NUM);
+
+    player_info.max_hp = difficulty_profile.player_max_health(PLAYER_MAX_HEALTH);
+    player_info.current_hp = player_info.max_hp;
+
+    let pickup_spawn_period = PICKUP_SPAWN_PERIOD * difficulty_profile.pickup_period_multiplier;
+    pickup_spawn_timer
+        .timer
+        .set_duration(Duration::from_secs_f32(pickup_spawn_period));
+    pickup_spawn_timer.timer.reset();
+}
+
 // When pickups go off the screen, despawn them
 pub fn despawn_pickups(
     mut commands: Commands,
     pickups_query: Query<(Entity, &Transform, &Pickup)>,
-    window_query: Query<&Window, With<PrimaryWindow>>,
+    mut run_stats: ResMut<RunStats>,
+    arena_bounds: Res<ArenaBounds>,
 ) {
-    let primary_window = window_query.get_single().unwrap();
-
     for (pickup_entity, pickup_transform, pickup_struct) in pickups_query.iter() {
-        if pickup_transform.translation.y > primary_window.height() + pickup_struct.collider.size.y
-        {
+        if pickup_transform.translation.y > arena_bounds.max.y + pickup_struct.collider.size.y {
+            run_stats.pickups_missed += 1;
             commands.entity(pickup_entity).despawn();
         }
     }
@@ -282,10 +464,30 @@ pub fn handle_game_over_event(
     }
 }
 
+// Pause action (Start on the gamepad or Escape by default) pauses and resumes the game
+pub fn toggle_pause(
+    mut next_game_state: ResMut<NextState<GameState>>,
+    action_state: Res<ActionState>,
+    game_state: Res<State<GameState>>,
+) {
+    if !action_state.just_pressed(InputAction::Pause) {
+        return;
+    }
+
+    next_game_state.set(if game_state.0 == GameState::Paused {
+        GameState::Running
+    } else {
+        GameState::Paused
+    });
+}
+
+// Converts the cursor and the steering touch into the world coordinates
 pub fn get_cursor_world_coordinates(
     mut game_info: ResMut<GameInfo>,
     window_query: Query<&Window, With<PrimaryWindow>>,
     camera_query: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    touch_controls: Res<TouchControls>,
+    touches: Res<Touches>,
 ) {
     let (camera, camera_transform) = camera_query.single();
 
@@ -293,13 +495,43 @@ pub fn get_cursor_world_coordinates(
 
     if let Some(cursor_world_position) = primary_window
         .cursor_position()
-        .and_then(|cursor| camera.viewport_to_world(camera_transform, cursor))
-        .map(|ray| ray.origin.truncate())
+        .and_then(|cursor| window_to_world(camera, camera_transform, cursor))
     {
         game_info.cursor_position = cursor_world_position;
     }
+
+    // Touches start at the top left corner, unlike the cursor
+    game_info.touch_position = touch_controls
+        .steering_touch
+        .and_then(|touch_id| touches.get_pressed(touch_id))
+        .map(|touch| {
+            Vec2::new(
+                touch.position().x,
+                primary_window.height() - touch.position().y,
+            )
+        })
+        .and_then(|touch| window_to_world(camera, camera_transform, touch));
 }
 
-pub fn tick_pickup_spawn_timer(time: Res<Time>, mut pickup_timer: ResMut<PickupSpawnTimer>) {
-    pickup_timer.timer.tick(time.delta());
+// Converts the window position (starting at the bottom left corner) into the world position
+pub fn window_to_world(
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+    window_position: Vec2,
+) -> Option<Vec2> {
+    camera
+        .viewport_to_world(camera_transform, window_position)
+        .map(|ray| ray.origin.truncate())
+}
+
+// Low fuel or health makes the pickups come sooner
+pub fn tick_pickup_spawn_timer(
+    time: Res<Time>,
+    player_info: Res<PlayerInfo>,
+    mut pickup_timer: ResMut<PickupSpawnTimer>,
+) {
+    pickup_timer.timer.tick(
+        time.delta()
+            .mul_f32(pickup_spawn_rate_multiplier(&player_info)),
+    );
 }
@@ -1,9 +1,15 @@
+mod achievements;
+mod controls;
 mod game;
 mod particle_system;
+mod settings;
 mod ui;
 
+use achievements::AchievementsPlugin;
+use controls::ControlsPlugin;
 use game::{GamePlugin, GameState};
 use particle_system::ParticleSystemPlugin;
+use settings::SettingsPlugin;
 use ui::UIPlugin;
 
 use bevy::{prelude::*, window::PresentMode};
@@ -29,8 +35,11 @@ fn main() {
                 .set(ImagePlugin::default_nearest()),
         )
         .add_state::<GameState>()
+        .add_plugin(SettingsPlugin)
+        .add_plugin(ControlsPlugin)
         .add_plugin(ParticleSystemPlugin)
         .add_plugin(GamePlugin)
+        .add_plugin(AchievementsPlugin)
         .add_plugin(UIPlugin)
         .run();
 }
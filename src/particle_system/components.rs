@@ -0,0 +1,20 @@
+use bevy::prelude::*;
+
+// Simple sprite, which drifts, fades in and fades out again over its lifetime
+#[derive(Component)]
+pub struct Particle {
+    pub velocity: Vec2,
+    pub lifetime: Timer,
+    // Alpha in the middle of the lifetime
+    pub alpha: f32,
+}
+
+impl Particle {
+    pub fn new(velocity: Vec2, lifetime: f32, alpha: f32) -> Self {
+        Particle {
+            velocity,
+            lifetime: Timer::from_seconds(lifetime, TimerMode::Once),
+            alpha,
+        }
+    }
+}
@@ -1,9 +1,15 @@
+pub mod components;
+mod systems;
+
+use crate::game::GameState;
+use systems::*;
+
 use bevy::prelude::*;
 
 pub struct ParticleSystemPlugin;
 
 impl Plugin for ParticleSystemPlugin {
     fn build(&self, app: &mut App) {
-        app;
+        app.add_system(update_particles.in_set(OnUpdate(GameState::Running)));
     }
 }
@@ -0,0 +1,25 @@
+use super::components::Particle;
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+
+pub fn update_particles(
+    mut commands: Commands,
+    mut particles_query: Query<(Entity, &mut Transform, &mut Sprite, &mut Particle)>,
+    time: Res<Time>,
+) {
+    for (particle_entity, mut particle_transform, mut particle_sprite, mut particle) in
+        particles_query.iter_mut()
+    {
+        if particle.lifetime.tick(time.delta()).finished() {
+            commands.entity(particle_entity).despawn();
+            continue;
+        }
+
+        particle_transform.translation += particle.velocity.extend(0.0) * time.delta_seconds();
+        particle_sprite
+            .color
+            .set_a(particle.alpha * (particle.lifetime.percent() * PI).sin());
+    }
+}
@@ -0,0 +1,112 @@
+use serde::{Deserialize, Serialize};
+
+// How the game constants are scaled for the chosen difficulty. Normal keeps them as is.
+pub struct DifficultyProfile {
+    pub name: &'static str,
+    // Multiplies the time between enemy spawns
+    pub spawn_period_multiplier: f32,
+    // Added to the max number of enemies in the arena
+    pub max_enemies_offset: i32,
+    pub enemy_health_multiplier: f32,
+    pub enemy_speed_multiplier: f32,
+    pub projectile_speed_multiplier: f32,
+    // Multiplies the time between pickup spawns
+    pub pickup_period_multiplier: f32,
+    // Added to the player's max health
+    pub player_health_offset: i32,
+    // Chance to ignite a touched enemy per second of the contact
+    pub burning_spread_chance_per_second: f32,
+    // Fire from the chainsaw is depth 0, every spread adds 1
+    pub burning_spread_max_chain_depth: u32,
+}
+
+impl DifficultyProfile {
+    pub fn max_enemies_num(&self, base: usize) -> usize {
+        (base as i32 + self.max_enemies_offset).max(1) as usize
+    }
+
+    pub fn player_max_health(&self, base: usize) -> usize {
+        (base as i32 + self.player_health_offset).max(1) as usize
+    }
+}
+
+const DIFFICULTY_PROFILES: [DifficultyProfile; 4] = [
+    DifficultyProfile {
+        name: "Easy",
+        spawn_period_multiplier: 1.3,
+        max_enemies_offset: -1,
+        enemy_health_multiplier: 0.75,
+        enemy_speed_multiplier: 0.85,
+        projectile_speed_multiplier: 0.8,
+        pickup_period_multiplier: 0.75,
+        player_health_offset: 2,
+        burning_spread_chance_per_second: 1.5,
+        burning_spread_max_chain_depth: 4,
+    },
+    DifficultyProfile {
+        name: "Normal",
+        spawn_period_multiplier: 1.0,
+        max_enemies_offset: 0,
+        enemy_health_multiplier: 1.0,
+        enemy_speed_multiplier: 1.0,
+        projectile_speed_multiplier: 1.0,
+        pickup_period_multiplier: 1.0,
+        player_health_offset: 0,
+        burning_spread_chance_per_second: 1.2,
+        burning_spread_max_chain_depth: 3,
+    },
+    DifficultyProfile {
+        name: "Hard",
+        spawn_period_multiplier: 0.8,
+        max_enemies_offset: 2,
+        enemy_health_multiplier: 1.25,
+        enemy_speed_multiplier: 1.1,
+        projectile_speed_multiplier: 1.15,
+        pickup_period_multiplier: 1.25,
+        player_health_offset: -1,
+        burning_spread_chance_per_second: 1.0,
+        burning_spread_max_chain_depth: 3,
+    },
+    DifficultyProfile {
+        name: "Nightmare",
+        spawn_period_multiplier: 0.6,
+        max_enemies_offset: 4,
+        enemy_health_multiplier: 1.6,
+        enemy_speed_multiplier: 1.25,
+        projectile_speed_multiplier: 1.3,
+        pickup_period_multiplier: 1.5,
+        player_health_offset: -2,
+        burning_spread_chance_per_second: 0.8,
+        burning_spread_max_chain_depth: 2,
+    },
+];
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum Difficulty {
+    Easy,
+    #[default]
+    Normal,
+    Hard,
+    Nightmare,
+}
+
+impl Difficulty {
+    pub fn next(&self) -> Self {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Nightmare,
+            Difficulty::Nightmare => Difficulty::Easy,
+        }
+    }
+
+    pub fn profile(&self) -> &'static DifficultyProfile {
+        &DIFFICULTY_PROFILES[*self as usize]
+    }
+
+    pub fn name(&self) -> &'static str {
+        self.profile().name
+    }
+}
@@ -0,0 +1,96 @@
+pub mod difficulty;
+pub mod resources;
+// There is no file system in the browser, so the config isn't stored there
+#[cfg(not(target_arch = "wasm32"))]
+mod systems;
+
+#[cfg(not(target_arch = "wasm32"))]
+use crate::controls::resources::InputBindings;
+use resources::*;
+#[cfg(not(target_arch = "wasm32"))]
+use systems::*;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+// Fraction of the player's top speed gained/lost per second with the keyboard scheme
+pub const KEYBOARD_ACCELERATION: f32 = 6.0;
+pub const KEYBOARD_DECELERATION: f32 = 8.0;
+
+// Settings and key bindings are stored next to the executable
+#[cfg(not(target_arch = "wasm32"))]
+const CONFIG_FILE_PATH: &str = "config.ron";
+
+pub struct SettingsPlugin;
+
+impl Plugin for SettingsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<GameSettings>();
+
+        #[cfg(not(target_arch = "wasm32"))]
+        app.add_startup_system(load_config)
+            .add_system(save_config.run_if(is_config_changed));
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ControlScheme {
+    // Player steers toward the cursor
+    #[default]
+    Mouse,
+    // Movement actions steer the player with acceleration
+    Keyboard,
+}
+
+impl ControlScheme {
+    pub fn next(&self) -> Self {
+        match self {
+            ControlScheme::Mouse => ControlScheme::Keyboard,
+            ControlScheme::Keyboard => ControlScheme::Mouse,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ControlScheme::Mouse => "Mouse",
+            ControlScheme::Keyboard => "Keyboard",
+        }
+    }
+}
+
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
+pub enum GameMode {
+    // Ends with the boss at MAX_DEPTH
+    #[default]
+    Standard,
+    // Depth keeps increasing and the boss keeps coming back
+    Endless,
+}
+
+impl GameMode {
+    pub fn next(&self) -> Self {
+        match self {
+            GameMode::Standard => GameMode::Endless,
+            GameMode::Endless => GameMode::Standard,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            GameMode::Standard => "Standard",
+            GameMode::Endless => "Endless",
+        }
+    }
+}
+
+// Contents of the config file
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Serialize, Deserialize)]
+struct ConfigFile {
+    #[serde(default)]
+    settings: GameSettings,
+    #[serde(default)]
+    bindings: InputBindings,
+}
@@ -0,0 +1,27 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::difficulty::Difficulty;
+use super::{ControlScheme, GameMode, KEYBOARD_ACCELERATION, KEYBOARD_DECELERATION};
+
+#[derive(Resource, Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct GameSettings {
+    pub control_scheme: ControlScheme,
+    pub keyboard_acceleration: f32,
+    pub keyboard_deceleration: f32,
+    pub difficulty: Difficulty,
+    pub game_mode: GameMode,
+}
+
+impl Default for GameSettings {
+    fn default() -> Self {
+        GameSettings {
+            control_scheme: ControlScheme::default(),
+            keyboard_acceleration: KEYBOARD_ACCELERATION,
+            keyboard_deceleration: KEYBOARD_DECELERATION,
+            difficulty: Difficulty::default(),
+            game_mode: GameMode::default(),
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use super::resources::GameSettings;
+use super::{ConfigFile, CONFIG_FILE_PATH};
+use crate::controls::resources::InputBindings;
+
+use bevy::prelude::*;
+
+// Replaces default settings and bindings with the saved ones, if there are any
+pub fn load_config(mut settings: ResMut<GameSettings>, mut input_bindings: ResMut<InputBindings>) {
+    let Ok(contents) = std::fs::read_to_string(CONFIG_FILE_PATH) else {
+        return;
+    };
+
+    match ron::from_str::<ConfigFile>(&contents) {
+        Ok(mut config) => {
+            // Actions added after the config was saved keep their default bindings
+            for (action, action_bindings) in InputBindings::default().bindings {
+                config
+                    .bindings
+                    .bindings
+                    .entry(action)
+                    .or_insert(action_bindings);
+            }
+
+            *settings = config.settings;
+            *input_bindings = config.bindings;
+        }
+        Err(error) => println!("Failed to parse {}: {}", CONFIG_FILE_PATH, error),
+    }
+}
+
+// Settings or bindings were changed by the player. Adding the resources and loading the config
+// on the start don't count, so the file isn't rewritten on every launch.
+pub fn is_config_changed(settings: Res<GameSettings>, input_bindings: Res<InputBindings>) -> bool {
+    (settings.is_changed() && !settings.is_added())
+        || (input_bindings.is_changed() && !input_bindings.is_added())
+}
+
+pub fn save_config(settings: Res<GameSettings>, input_bindings: Res<InputBindings>) {
+    let config = ConfigFile {
+        settings: settings.clone(),
+        bindings: input_bindings.clone(),
+    };
+
+    let contents = match ron::ser::to_string_pretty(&config, ron::ser::PrettyConfig::default()) {
+        Ok(contents) => contents,
+        Err(error) => {
+            println!("Failed to serialize the config: {}", error);
+            return;
+        }
+    };
+
+    if let Err(error) = std::fs::write(CONFIG_FILE_PATH, contents) {
+        println!("Failed to save {}: {}", CONFIG_FILE_PATH, error);
+    }
+}
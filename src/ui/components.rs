@@ -1,3 +1,6 @@
+use crate::controls::InputAction;
+use crate::game::pickups::PickupKind;
+
 use bevy::prelude::*;
 
 #[derive(Component)]
@@ -6,12 +9,68 @@ pub struct MainMenu;
 #[derive(Component)]
 pub struct DepthBarIcon;
 
+// Replaces the depth bar in the endless mode
+#[derive(Component)]
+pub struct DepthText;
+
 #[derive(Component)]
 pub struct PlayButton;
 
+#[derive(Component)]
+pub struct SettingsButton;
+
+#[derive(Component)]
+pub struct SettingsMenu;
+
+#[derive(Component)]
+pub struct AchievementsButton;
+
+#[derive(Component)]
+pub struct AchievementsMenu;
+
+#[derive(Component)]
+pub struct DifficultyButton;
+
+#[derive(Component)]
+pub struct DifficultyText;
+
+#[derive(Component)]
+pub struct GameModeButton;
+
+#[derive(Component)]
+pub struct GameModeText;
+
+#[derive(Component)]
+pub struct ControlSchemeButton;
+
+#[derive(Component)]
+pub struct ControlSchemeText;
+
+#[derive(Component)]
+pub struct RebindButton {
+    pub action: InputAction,
+}
+
+#[derive(Component)]
+pub struct RebindText {
+    pub action: InputAction,
+}
+
+#[derive(Component)]
+pub struct BackButton;
+
 #[derive(Component)]
 pub struct GameUI;
 
+#[derive(Component)]
+pub struct HudLeftPanel;
+
+#[derive(Component)]
+pub struct HudRightPanel;
+
+#[derive(Component)]
+pub struct TouchControlsUI;
+
 #[derive(Component)]
 pub struct FuelText;
 
@@ -20,3 +79,43 @@ pub struct HealthText;
 
 #[derive(Component)]
 pub struct BloodText;
+
+#[derive(Component)]
+pub struct ComboText;
+
+#[derive(Component)]
+pub struct BossHealthBar;
+
+#[derive(Component)]
+pub struct BossHealthBarFill;
+
+#[derive(Component)]
+pub struct ChainsawPromptImage;
+
+#[derive(Component)]
+pub struct ChainsawPromptText;
+
+#[derive(Component)]
+pub struct PauseMenu;
+
+#[derive(Component)]
+pub struct GameOverSummary;
+
+#[derive(Component)]
+pub struct AchievementToastList;
+
+// Row of a lasting pickup with its remaining time, hidden while it isn't active
+#[derive(Component)]
+pub struct PickupEffectTimer {
+    pub kind: PickupKind,
+}
+
+#[derive(Component)]
+pub struct PickupEffectTimerText {
+    pub kind: PickupKind,
+}
+
+#[derive(Component)]
+pub struct AchievementToast {
+    pub timer: Timer,
+}
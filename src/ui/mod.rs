@@ -3,22 +3,108 @@ mod styles;
 mod systems;
 
 use super::game::GameState;
+use crate::controls::gamepad_is_active;
 use styles::*;
 use systems::*;
 
 use bevy::prelude::*;
 
+// Windows with height / width above this get the portrait HUD layout
+const PORTRAIT_ASPECT_RATIO: f32 = 1.5;
+
+const ACHIEVEMENT_TOAST_DURATION: f32 = 3.0;
+
 pub struct UIPlugin;
 
 impl Plugin for UIPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems((
-            spawn_main_menu.in_schedule(OnEnter(GameState::MainMenu)),
-            despawn_main_menu.in_schedule(OnExit(GameState::MainMenu)),
-            interact_with_play_button.run_if(in_state(GameState::MainMenu)),
-            spawn_game_ui.in_schedule(OnExit(GameState::MainMenu)),
-            update_depth_ui.run_if(in_state(GameState::Running)),
-            update_ui_text.run_if(in_state(GameState::Running)),
-        ));
+        app.add_state::<MenuPage>()
+            .init_resource::<MenuFocus>()
+            .add_systems((
+                spawn_main_menu.in_schedule(OnEnter(GameState::MainMenu)),
+                despawn_main_menu.in_schedule(OnExit(GameState::MainMenu)),
+                interact_with_play_button.run_if(in_state(GameState::MainMenu)),
+                interact_with_settings_button.run_if(in_state(GameState::MainMenu)),
+                interact_with_achievements_button.run_if(in_state(GameState::MainMenu)),
+                spawn_game_ui.in_schedule(OnExit(GameState::MainMenu)),
+                update_depth_ui.run_if(in_state(GameState::Running)),
+                update_ui_text.run_if(in_state(GameState::Running)),
+                update_chainsaw_prompt.run_if(in_state(GameState::Running)),
+                update_boss_health_bar.run_if(in_state(GameState::Running)),
+                update_combo_text.run_if(in_state(GameState::Running)),
+                update_touch_controls_visibility.run_if(in_state(GameState::Running)),
+                adapt_hud_to_aspect_ratio.run_if(in_state(GameState::Running)),
+                spawn_pause_menu.in_schedule(OnEnter(GameState::Paused)),
+                despawn_pause_menu.in_schedule(OnExit(GameState::Paused)),
+            ))
+            .add_systems(
+                (
+                    interact_with_difficulty_button,
+                    update_difficulty_text,
+                    interact_with_game_mode_button,
+                    update_game_mode_text,
+                )
+                    .distributive_run_if(in_state(GameState::MainMenu)),
+            )
+            .add_system(spawn_game_over_summary.in_schedule(OnEnter(GameState::Gameover)))
+            .add_systems(
+                (
+                    spawn_achievement_toasts,
+                    despawn_expired_achievement_toasts,
+                    update_pickup_effect_timers,
+                )
+                    .in_set(OnUpdate(GameState::Running)),
+            )
+            // D-pad moves the focus between the menu buttons, A presses the focused one
+            .add_systems(
+                (
+                    navigate_menu_with_gamepad,
+                    highlight_focused_button.after(navigate_menu_with_gamepad),
+                )
+                    .distributive_run_if(in_state(GameState::MainMenu))
+                    .distributive_run_if(gamepad_is_active),
+            )
+            // Settings page replaces the main menu, and brings it back upon leaving
+            .add_systems((
+                reset_menu_focus.in_schedule(OnEnter(MenuPage::Settings)),
+                reset_menu_focus.in_schedule(OnExit(MenuPage::Settings)),
+                despawn_main_menu.in_schedule(OnEnter(MenuPage::Settings)),
+                spawn_settings_menu.in_schedule(OnEnter(MenuPage::Settings)),
+                despawn_settings_menu.in_schedule(OnExit(MenuPage::Settings)),
+                spawn_main_menu.in_schedule(OnExit(MenuPage::Settings)),
+                interact_with_control_scheme_button.run_if(in_state(MenuPage::Settings)),
+                update_control_scheme_text.run_if(in_state(MenuPage::Settings)),
+                interact_with_rebind_buttons.run_if(in_state(MenuPage::Settings)),
+                update_rebind_text.run_if(in_state(MenuPage::Settings)),
+            ))
+            // Achievements page works the same way
+            .add_systems((
+                reset_menu_focus.in_schedule(OnEnter(MenuPage::Achievements)),
+                reset_menu_focus.in_schedule(OnExit(MenuPage::Achievements)),
+                despawn_main_menu.in_schedule(OnEnter(MenuPage::Achievements)),
+                spawn_achievements_menu.in_schedule(OnEnter(MenuPage::Achievements)),
+                despawn_achievements_menu.in_schedule(OnExit(MenuPage::Achievements)),
+                spawn_main_menu.in_schedule(OnExit(MenuPage::Achievements)),
+                interact_with_back_button
+                    .run_if(in_state(MenuPage::Settings).or_else(in_state(MenuPage::Achievements))),
+            ));
     }
 }
+
+// Which page of the main menu is shown
+#[derive(States, Default, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MenuPage {
+    #[default]
+    Main,
+    Settings,
+    Achievements,
+}
+
+// Button of the current menu page, which is selected with the gamepad
+#[derive(Resource, Default)]
+pub struct MenuFocus {
+    // Index of the button, counting from the top of the page
+    pub index: usize,
+    // Button pressed with the gamepad last frame, which has to be released
+    pub pressed_button: Option<Entity>,
+}
@@ -43,6 +43,131 @@ pub const CENTER_STYLE: Style = Style {
     ..Style::DEFAULT
 };
 
+// Tall screens (phones) stack the HUD panels on top of each other
+pub const PORTRAIT_GAME_HUD_STYLE: Style = Style {
+    display: Display::Flex,
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::FlexStart,
+    align_items: AlignItems::FlexStart,
+    size: Size::new(Val::Percent(100.0), Val::Auto),
+    ..Style::DEFAULT
+};
+
+pub const PORTRAIT_LHS_STYLE: Style = Style {
+    display: Display::Flex,
+    flex_direction: FlexDirection::Column,
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::FlexStart,
+    size: Size::new(Val::Auto, Val::Auto),
+    margin: UiRect::new(Val::Px(8.0), Val::Px(0.0), Val::Px(8.0), Val::Px(0.0)),
+    ..Style::DEFAULT
+};
+
+pub const PORTRAIT_RHS_STYLE: Style = Style {
+    display: Display::Flex,
+    flex_direction: FlexDirection::Row,
+    justify_content: JustifyContent::FlexStart,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Auto, Val::Auto),
+    margin: UiRect::new(Val::Px(16.0), Val::Px(0.0), Val::Px(0.0), Val::Px(0.0)),
+    ..Style::DEFAULT
+};
+
+pub const TOUCH_CONTROLS_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        right: Val::Px(16.0),
+        bottom: Val::Px(16.0),
+        ..UiRect::DEFAULT
+    },
+    flex_direction: FlexDirection::Column,
+    gap: Size::new(Val::Px(16.0), Val::Px(16.0)),
+    ..Style::DEFAULT
+};
+
+pub const TOUCH_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(96.0), Val::Px(96.0)),
+    ..Style::DEFAULT
+};
+
+// Hidden, until the boss shows up
+pub const BOSS_BAR_STYLE: Style = Style {
+    display: Display::None,
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        top: Val::Px(16.0),
+        ..UiRect::DEFAULT
+    },
+    flex_direction: FlexDirection::Column,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Percent(100.0), Val::Auto),
+    gap: Size::new(Val::Px(4.0), Val::Px(4.0)),
+    ..Style::DEFAULT
+};
+
+pub const BOSS_BAR_BACKGROUND_STYLE: Style = Style {
+    size: Size::new(Val::Px(480.0), Val::Px(20.0)),
+    ..Style::DEFAULT
+};
+
+pub const BOSS_BAR_BACKGROUND_COLOR: Color = Color::rgb(0.1, 0.1, 0.1);
+pub const BOSS_BAR_FILL_COLOR: Color = Color::rgb(0.75, 0.1, 0.1);
+pub const BOSS_BAR_PHASE_MARKER_COLOR: Color = Color::rgb(0.95, 0.85, 0.4);
+
+// Unlocked achievements pop up in the bottom left corner
+pub const ACHIEVEMENT_TOAST_LIST_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        left: Val::Px(16.0),
+        bottom: Val::Px(16.0),
+        ..UiRect::DEFAULT
+    },
+    flex_direction: FlexDirection::ColumnReverse,
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
+// Timers of the active pickups, on the opposite side of the depth bar
+pub const PICKUP_EFFECT_LIST_STYLE: Style = Style {
+    position_type: PositionType::Absolute,
+    position: UiRect {
+        left: Val::Px(20.0),
+        top: Val::Px(250.0),
+        ..UiRect::DEFAULT
+    },
+    flex_direction: FlexDirection::Column,
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
+pub const PICKUP_EFFECT_TIMER_STYLE: Style = Style {
+    display: Display::None,
+    flex_direction: FlexDirection::Row,
+    align_items: AlignItems::Center,
+    gap: Size::new(Val::Px(8.0), Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
+pub const PICKUP_EFFECT_ICON_SIZE: Size = Size::new(Val::Px(32.0), Val::Px(32.0));
+
+pub const ACHIEVEMENT_TOAST_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    padding: UiRect::all(Val::Px(8.0)),
+    ..Style::DEFAULT
+};
+
+pub const ACHIEVEMENT_ENTRY_STYLE: Style = Style {
+    flex_direction: FlexDirection::Column,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(360.0), Val::Auto),
+    ..Style::DEFAULT
+};
+
+pub const ACHIEVEMENT_TOAST_COLOR: Color = Color::rgba(0.0, 0.0, 0.0, 0.7);
+pub const LOCKED_ACHIEVEMENT_TEXT_COLOR: Color = Color::rgb(0.45, 0.45, 0.45);
+
 pub const MAIN_MENU_STYLE: Style = Style {
     flex_direction: FlexDirection::Column,
     justify_content: JustifyContent::Center,
@@ -59,6 +184,20 @@ pub const BUTTON_STYLE: Style = Style {
     ..Style::DEFAULT
 };
 
+pub const WIDE_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(360.0), Val::Px(80.0)),
+    ..Style::DEFAULT
+};
+
+pub const BINDING_BUTTON_STYLE: Style = Style {
+    justify_content: JustifyContent::Center,
+    align_items: AlignItems::Center,
+    size: Size::new(Val::Px(360.0), Val::Px(36.0)),
+    ..Style::DEFAULT
+};
+
 pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     TextStyle {
         font: asset_server.load("fonts/origami_mommy_regular.ttf"),
@@ -67,6 +206,14 @@ pub fn get_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
     }
 }
 
+pub fn get_small_button_text_style(asset_server: &Res<AssetServer>) -> TextStyle {
+    TextStyle {
+        font: asset_server.load("fonts/origami_mommy_regular.ttf"),
+        font_size: 24.0,
+        color: Color::WHITE,
+    }
+}
+
 // pub const MOUSE_STYLE: Style = Style {
 //     display: Display::Flex,
 //     flex_direction: FlexDirection::Row,
@@ -1,11 +1,33 @@
 use super::components::*;
 use super::styles::*;
+use super::{MenuFocus, MenuPage, ACHIEVEMENT_TOAST_DURATION, PORTRAIT_ASPECT_RATIO};
 use super::{HOVERED_BUTTON_COLOR, NORMAL_BUTTON_COLOR, PRESSED_BUTTON_COLOR};
+use crate::achievements::resources::{HighScores, UnlockedAchievements};
+use crate::achievements::{AchievementUnlockedEvent, ACHIEVEMENTS};
+use crate::controls::components::TouchActionButton;
+use crate::controls::resources::{ActionState, ActiveInputDevice, InputBindings, RebindState};
+use crate::controls::{InputAction, InputBinding, InputDevice};
+use crate::game::combo::resources::Combo;
+use crate::game::enemy::components::{BossAI, Enemy};
+use crate::game::enemy::{BOSS_NAME, BOSS_PHASES};
+use crate::game::pickups::resources::ActivePickupEffects;
+use crate::game::pickups::{PickupDuration, PICKUPS};
 use crate::game::player::{PLAYER_FUEL_CAPACITY, PLAYER_MAX_HEALTH};
 use crate::game::GameInfo;
 use crate::game::{player::resources::PlayerInfo, GameState};
+use crate::settings::resources::GameSettings;
+use crate::settings::{ControlScheme, GameMode};
 
 use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+
+// Menu button with the marker T, whose interaction changed this frame
+type ButtonInteractionQuery<'w, 's, T> = Query<
+    'w,
+    's,
+    (&'static Interaction, &'static mut BackgroundColor),
+    (Changed<Interaction>, With<T>),
+>;
 
 // Updates all the game ui, if the player_info got changed
 pub fn update_ui_text(
@@ -29,20 +51,116 @@ pub fn update_ui_text(
 pub fn update_depth_ui(
     game_info: Res<GameInfo>,
     mut depth_bar_icon_query: Query<&mut Style, With<DepthBarIcon>>,
+    mut depth_text_query: Query<&mut Text, With<DepthText>>,
 ) {
     if game_info.is_changed() {
-        let mut depth_bar_icon = depth_bar_icon_query.get_single_mut().unwrap();
+        if let Ok(mut depth_bar_icon) = depth_bar_icon_query.get_single_mut() {
+            depth_bar_icon.position.top = Val::Px(game_info.player_progress);
+            // println!("Icon translation: {}", depth_bar_icon.position);
+        }
+
+        for mut depth_text in depth_text_query.iter_mut() {
+            depth_text.sections[0].value = format!("Depth {}", game_info.player_progress.floor());
+        }
+    }
+}
+
+// Combo is shown only while it's going
+pub fn update_combo_text(
+    mut combo_text_query: Query<&mut Text, With<ComboText>>,
+    combo: Res<Combo>,
+) {
+    if !combo.is_changed() {
+        return;
+    }
+
+    for mut combo_text in combo_text_query.iter_mut() {
+        let value = if combo.count > 0 {
+            format!("Combo {}  x{:.1}", combo.count, combo.multiplier())
+        } else {
+            String::new()
+        };
+
+        if combo_text.sections[0].value != value {
+            combo_text.sections[0].value = value;
+        }
+    }
+}
+
+// Shows the remaining seconds of every active pickup
+pub fn update_pickup_effect_timers(
+    mut pickup_effect_timer_query: Query<(&mut Style, &PickupEffectTimer)>,
+    mut pickup_effect_timer_text_query: Query<(&mut Text, &PickupEffectTimerText)>,
+    active_pickup_effects: Res<ActivePickupEffects>,
+) {
+    for (mut style, pickup_effect_timer) in pickup_effect_timer_query.iter_mut() {
+        let display = if active_pickup_effects.is_active(pickup_effect_timer.kind) {
+            Display::Flex
+        } else {
+            Display::None
+        };
+
+        if style.display != display {
+            style.display = display;
+        }
+    }
+
+    for (mut text, pickup_effect_timer_text) in pickup_effect_timer_text_query.iter_mut() {
+        let Some(active_effect) = active_pickup_effects.get(pickup_effect_timer_text.kind) else {
+            continue;
+        };
+
+        let value = match &active_effect.timer {
+            Some(timer) => format!(
+                "{} {:.0}s",
+                active_effect.pickup.name,
+                timer.remaining_secs().ceil()
+            ),
+            None => active_effect.pickup.name.to_string(),
+        };
+        if text.sections[0].value != value {
+            text.sections[0].value = value;
+        }
+    }
+}
+
+// Shows the boss bar while the boss is alive
+pub fn update_boss_health_bar(
+    game_info: Res<GameInfo>,
+    boss_query: Query<&Enemy, With<BossAI>>,
+    mut boss_bar_query: Query<&mut Style, (With<BossHealthBar>, Without<BossHealthBarFill>)>,
+    mut boss_bar_fill_query: Query<&mut Style, (With<BossHealthBarFill>, Without<BossHealthBar>)>,
+) {
+    let boss = boss_query
+        .get_single()
+        .ok()
+        .filter(|_| game_info.is_boss_spawned);
+
+    for mut boss_bar_style in boss_bar_query.iter_mut() {
+        let display = if boss.is_some() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+
+        if boss_bar_style.display != display {
+            boss_bar_style.display = display;
+        }
+    }
+
+    if let Some(boss_struct) = boss {
+        let health_percent = (boss_struct.current_hp / boss_struct.max_hp).clamp(0.0, 1.0) * 100.0;
 
-        depth_bar_icon.position.top = Val::Px(game_info.player_progress);
-        // println!("Icon translation: {}", depth_bar_icon.position);
+        for mut boss_bar_fill_style in boss_bar_fill_query.iter_mut() {
+            if boss_bar_fill_style.size.width != Val::Percent(health_percent) {
+                boss_bar_fill_style.size.width = Val::Percent(health_percent);
+            }
+        }
     }
 }
 
 pub fn interact_with_play_button(
-    mut button_query: Query<
-        (&Interaction, &mut BackgroundColor),
-        (Changed<Interaction>, With<PlayButton>),
-    >,
+    mut button_query: ButtonInteractionQuery<PlayButton>,
     mut app_state_next_state: ResMut<NextState<GameState>>,
 ) {
     if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
@@ -61,240 +179,1345 @@ pub fn interact_with_play_button(
     }
 }
 
-pub fn spawn_game_ui(mut commands: Commands, asset_server: Res<AssetServer>) {
-    build_game_ui(&mut commands, &asset_server);
-    build_depth_ui(&mut commands, &asset_server);
+pub fn interact_with_settings_button(
+    mut button_query: ButtonInteractionQuery<SettingsButton>,
+    mut next_menu_page: ResMut<NextState<MenuPage>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                next_menu_page.set(MenuPage::Settings);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
 }
 
-pub fn spawn_main_menu(mut commands: Commands, asset_server: Res<AssetServer>) {
-    build_main_menu(&mut commands, &asset_server);
+pub fn interact_with_achievements_button(
+    mut button_query: ButtonInteractionQuery<AchievementsButton>,
+    mut next_menu_page: ResMut<NextState<MenuPage>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                next_menu_page.set(MenuPage::Achievements);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
 }
 
-pub fn despawn_main_menu(mut commands: Commands, main_menu_query: Query<Entity, With<MainMenu>>) {
-    if let Ok(main_menu_entity) = main_menu_query.get_single() {
-        commands.entity(main_menu_entity).despawn_recursive();
+// Cycles through the difficulties, the chosen one is saved with the settings
+pub fn interact_with_difficulty_button(
+    mut button_query: ButtonInteractionQuery<DifficultyButton>,
+    mut settings: ResMut<GameSettings>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                settings.difficulty = settings.difficulty.next();
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
     }
 }
 
-pub fn build_depth_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
-    // ===== Depth bar background =====
-    commands
-        .spawn(ImageBundle {
-            style: Style {
-                size: Size::new(Val::Px(25.0 * 2.), Val::Px(128. * 2.)),
+pub fn update_difficulty_text(
+    mut difficulty_text_query: Query<&mut Text, With<DifficultyText>>,
+    settings: Res<GameSettings>,
+) {
+    if settings.is_changed() {
+        for mut difficulty_text in difficulty_text_query.iter_mut() {
+            difficulty_text.sections[0].value =
+                format!("Difficulty: {}", settings.difficulty.name());
+        }
+    }
+}
 
-                position_type: PositionType::Absolute,
-                position: UiRect {
-                    left: Val::Px(450.),
-                    right: Val::Px(20.),
-                    top: Val::Px(250.),
-                    bottom: Val::Px(250.),
-                },
-                ..default()
-            },
-            image: asset_server
-                .load("sprites/progress_bar_background.png")
-                .into(),
-            ..default()
-        })
-        .with_children(|parent| {
-            // ===== Depth bar icon =====
-            parent.spawn((
-                ImageBundle {
-                    style: Style {
-                        size: Size::new(Val::Px(22.0 * 2.), Val::Px(19.0 * 2.)),
-                        position_type: PositionType::Absolute,
-                        // margin: UiRect::new(Val::Px(0.), Val::Px(0.), Val::Px(0.), Val::Px(1000.)),
-                        position: UiRect::new(Val::Px(0.), Val::Px(0.), Val::Px(0.), Val::Px(0.)),
-                        ..default()
-                    },
-                    z_index: ZIndex::Local(1),
-                    image: asset_server.load("sprites/progress_bar_icon.png").into(),
-                    ..default()
-                },
-                DepthBarIcon {},
-            ));
-        })
-        .id()
+pub fn interact_with_game_mode_button(
+    mut button_query: ButtonInteractionQuery<GameModeButton>,
+    mut settings: ResMut<GameSettings>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                settings.game_mode = settings.game_mode.next();
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
 }
 
-pub fn build_game_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
-    commands
-        .spawn((
-            NodeBundle {
-                style: GAME_HUD_STYLE,
-                ..default()
-            },
-            GameUI {},
-        ))
-        .with_children(|parent| {
-            // LHS UI elements
-            parent
-                .spawn(NodeBundle {
-                    style: LHS_STYLE,
-                    ..default()
-                })
-                .with_children(|parent| {
-                    // === Health bar === (LHS, top)
-                    parent
-                        .spawn(
-                            // Health bar image
-                            ImageBundle {
-                                style: Style {
-                                    size: Size::new(Val::Px(129.0), Val::Px(53.0)),
-                                    margin: UiRect::new(
-                                        Val::Px(8.0),
-                                        Val::Px(8.0),
-                                        Val::Px(8.0),
-                                        Val::Px(8.0),
-                                    ),
-                                    ..default()
-                                },
-                                image: asset_server.load("sprites/health_bg.png").into(),
-                                ..default()
-                            },
-                        )
-                        .with_children(|parent| {
-                            parent.spawn(
-                                // Health bar text
-                                (
-                                    TextBundle {
-                                        style: Style {
-                                            margin: UiRect::new(
-                                                Val::Px(8.0),
-                                                Val::Px(0.0),
-                                                Val::Px(4.0),
-                                                Val::Px(0.0),
-                                            ),
-                                            ..default()
-                                        },
-                                        text: Text {
-                                            sections: vec![TextSection::new(
-                                                PLAYER_MAX_HEALTH.to_string(),
-                                                TextStyle {
-                                                    font: asset_server
-                                                        .load("fonts/origami_mommy_regular.ttf"),
-                                                    font_size: 48.0,
-                                                    color: Color::WHITE,
-                                                },
-                                            )],
-                                            alignment: TextAlignment::Center,
-                                            ..default()
-                                        },
-                                        ..default()
-                                    },
-                                    HealthText {},
-                                ),
-                            );
+pub fn update_game_mode_text(
+    mut game_mode_text_query: Query<&mut Text, With<GameModeText>>,
+    settings: Res<GameSettings>,
+) {
+    if settings.is_changed() {
+        for mut game_mode_text in game_mode_text_query.iter_mut() {
+            game_mode_text.sections[0].value = format!("Mode: {}", settings.game_mode.name());
+        }
+    }
+}
 
-                            parent.spawn(
-                                // Health image
-                                ImageBundle {
-                                    style: Style {
-                                        size: Size::new(Val::Px(30.0), Val::Px(30.0)),
-                                        margin: UiRect::new(
-                                            Val::Px(8.0),
-                                            Val::Px(8.0),
-                                            Val::Px(8.0),
-                                            Val::Px(8.0),
-                                        ),
-                                        ..default()
-                                    },
-                                    image: asset_server.load("sprites/health.png").into(),
-                                    ..default()
-                                },
-                            );
-                        });
+// Cycles through the available control schemes
+pub fn interact_with_control_scheme_button(
+    mut button_query: ButtonInteractionQuery<ControlSchemeButton>,
+    mut settings: ResMut<GameSettings>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                settings.control_scheme = settings.control_scheme.next();
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
 
-                    // === Fuel bar === (LHS, bottom)
-                    parent
-                        .spawn(
-                            // Fuel bar image
-                            ImageBundle {
-                                style: Style {
-                                    size: Size::new(Val::Px(129.0), Val::Px(53.0)),
-                                    margin: UiRect::new(
-                                        Val::Px(8.0),
-                                        Val::Px(8.0),
-                                        Val::Px(8.0),
-                                        Val::Px(8.0),
-                                    ),
-                                    ..default()
-                                },
+pub fn update_control_scheme_text(
+    mut control_scheme_text_query: Query<&mut Text, With<ControlSchemeText>>,
+    settings: Res<GameSettings>,
+) {
+    if settings.is_changed() {
+        for mut control_scheme_text in control_scheme_text_query.iter_mut() {
+            control_scheme_text.sections[0].value =
+                format!("Controls: {}", settings.control_scheme.name());
+        }
+    }
+}
+
+// Clicked action waits for its new button (see capture_rebinding)
+pub fn interact_with_rebind_buttons(
+    mut button_query: Query<
+        (&Interaction, &mut BackgroundColor, &RebindButton),
+        Changed<Interaction>,
+    >,
+    mut rebind_state: ResMut<RebindState>,
+) {
+    for (interaction, mut background_color, rebind_button) in button_query.iter_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                rebind_state.action = Some(rebind_button.action);
+                rebind_state.armed = false;
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+pub fn update_rebind_text(
+    mut rebind_text_query: Query<(&mut Text, &RebindText)>,
+    input_bindings: Res<InputBindings>,
+    rebind_state: Res<RebindState>,
+) {
+    if !input_bindings.is_changed() && !rebind_state.is_changed() {
+        return;
+    }
+
+    for (mut text, rebind_text) in rebind_text_query.iter_mut() {
+        text.sections[0].value =
+            get_rebind_text(rebind_text.action, &input_bindings, &rebind_state);
+    }
+}
+
+fn get_rebind_text(
+    action: InputAction,
+    input_bindings: &InputBindings,
+    rebind_state: &RebindState,
+) -> String {
+    if rebind_state.action == Some(action) {
+        return format!("{}: press a button...", action.name());
+    }
+
+    format!(
+        "{}: {} / {}",
+        action.name(),
+        input_bindings.prompt(action, InputDevice::KeyboardMouse),
+        input_bindings.prompt(action, InputDevice::Gamepad)
+    )
+}
+
+pub fn interact_with_back_button(
+    mut button_query: ButtonInteractionQuery<BackButton>,
+    mut next_menu_page: ResMut<NextState<MenuPage>>,
+) {
+    if let Ok((interaction, mut background_color)) = button_query.get_single_mut() {
+        match *interaction {
+            Interaction::Clicked => {
+                *background_color = PRESSED_BUTTON_COLOR.into();
+                next_menu_page.set(MenuPage::Main);
+            }
+            Interaction::Hovered => {
+                *background_color = HOVERED_BUTTON_COLOR.into();
+            }
+            Interaction::None => {
+                *background_color = NORMAL_BUTTON_COLOR.into();
+            }
+        }
+    }
+}
+
+// Returns buttons of the current menu page from the top to the bottom
+fn sorted_menu_buttons(
+    button_query: &Query<(Entity, &GlobalTransform, &mut Interaction), With<Button>>,
+) -> Vec<Entity> {
+    let mut buttons: Vec<(Entity, f32)> = button_query
+        .iter()
+        .map(|(entity, transform, _)| (entity, transform.translation().y))
+        .collect();
+    buttons.sort_by(|(_, a), (_, b)| a.total_cmp(b));
+
+    buttons.into_iter().map(|(entity, _)| entity).collect()
+}
+
+// Move actions (D-pad by default) change the focused button, Confirm (A) presses it.
+// The focused button is pressed by setting its interaction, so the buttons
+// react to the gamepad the same way they react to the mouse.
+pub fn navigate_menu_with_gamepad(
+    mut menu_focus: ResMut<MenuFocus>,
+    mut button_query: Query<(Entity, &GlobalTransform, &mut Interaction), With<Button>>,
+    action_state: Res<ActionState>,
+) {
+    // Release the button pressed last frame
+    if let Some(pressed_button) = menu_focus.pressed_button.take() {
+        if let Ok((_, _, mut interaction)) = button_query.get_mut(pressed_button) {
+            *interaction = Interaction::None;
+        }
+    }
+
+    let buttons = sorted_menu_buttons(&button_query);
+    if buttons.is_empty() {
+        return;
+    }
+
+    if action_state.just_pressed(InputAction::MoveDown) {
+        menu_focus.index = (menu_focus.index + 1) % buttons.len();
+    }
+    if action_state.just_pressed(InputAction::MoveUp) {
+        menu_focus.index = (menu_focus.index + buttons.len() - 1) % buttons.len();
+    }
+    menu_focus.index = menu_focus.index.min(buttons.len() - 1);
+
+    if action_state.just_pressed(InputAction::Confirm) {
+        let focused_button = buttons[menu_focus.index];
+
+        if let Ok((_, _, mut interaction)) = button_query.get_mut(focused_button) {
+            *interaction = Interaction::Clicked;
+            menu_focus.pressed_button = Some(focused_button);
+        }
+    }
+}
+
+pub fn highlight_focused_button(
+    mut button_query: Query<(Entity, &GlobalTransform, &mut Interaction), With<Button>>,
+    mut background_color_query: Query<&mut BackgroundColor, With<Button>>,
+    menu_focus: Res<MenuFocus>,
+) {
+    let buttons = sorted_menu_buttons(&button_query);
+
+    for (index, button) in buttons.into_iter().enumerate() {
+        let Ok((_, _, interaction)) = button_query.get_mut(button) else {
+            continue;
+        };
+        if *interaction != Interaction::None {
+            continue;
+        }
+
+        if let Ok(mut background_color) = background_color_query.get_mut(button) {
+            *background_color = if index == menu_focus.index {
+                HOVERED_BUTTON_COLOR.into()
+            } else {
+                NORMAL_BUTTON_COLOR.into()
+            };
+        }
+    }
+}
+
+pub fn reset_menu_focus(mut menu_focus: ResMut<MenuFocus>) {
+    menu_focus.index = 0;
+}
+
+type ChainsawPromptImageQuery<'w, 's> = Query<
+    'w,
+    's,
+    (&'static mut Style, &'static mut UiImage),
+    (With<ChainsawPromptImage>, Without<ChainsawPromptText>),
+>;
+
+// Shows the mouse icon for mouse buttons, and the button name otherwise.
+// Prefers the binding, which fits the selected control scheme.
+pub fn update_chainsaw_prompt(
+    mut prompt_image_query: ChainsawPromptImageQuery,
+    mut prompt_text_query: Query<(&mut Style, &mut Text), With<ChainsawPromptText>>,
+    active_input_device: Res<ActiveInputDevice>,
+    input_bindings: Res<InputBindings>,
+    settings: Res<GameSettings>,
+    asset_server: Res<AssetServer>,
+) {
+    if !active_input_device.is_changed() && !input_bindings.is_changed() && !settings.is_changed() {
+        return;
+    }
+
+    let keyboard_mouse_binding =
+        input_bindings
+            .get(InputAction::ActivateChainsaw)
+            .and_then(|action_bindings| {
+                action_bindings
+                    .keyboard_mouse
+                    .iter()
+                    .find(|binding| {
+                        matches!(
+                            (binding, settings.control_scheme),
+                            (InputBinding::Mouse(_), ControlScheme::Mouse)
+                                | (InputBinding::Key(_), ControlScheme::Keyboard)
+                        )
+                    })
+                    .or(action_bindings.keyboard_mouse.first())
+                    .copied()
+            });
+
+    let prompt_image = match (active_input_device.device, keyboard_mouse_binding) {
+        (InputDevice::KeyboardMouse, Some(InputBinding::Mouse(MouseButton::Left))) => {
+            Some("sprites/LMB.png")
+        }
+        (InputDevice::KeyboardMouse, Some(InputBinding::Mouse(MouseButton::Right))) => {
+            Some("sprites/RMB.png")
+        }
+        _ => None,
+    };
+    let prompt_text = match (active_input_device.device, keyboard_mouse_binding) {
+        (InputDevice::KeyboardMouse, Some(binding)) => binding.name(),
+        (device, _) => input_bindings.prompt(InputAction::ActivateChainsaw, device),
+    };
+
+    for (mut image_style, mut image) in prompt_image_query.iter_mut() {
+        if let Some(prompt_image) = prompt_image {
+            image_style.display = Display::Flex;
+            image.texture = asset_server.load(prompt_image);
+        } else {
+            image_style.display = Display::None;
+        }
+    }
+
+    for (mut text_style, mut text) in prompt_text_query.iter_mut() {
+        text_style.display = if prompt_image.is_none() {
+            Display::Flex
+        } else {
+            Display::None
+        };
+        text.sections[0].value = prompt_text.clone();
+    }
+}
+
+pub fn spawn_pause_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    active_input_device: Res<ActiveInputDevice>,
+    input_bindings: Res<InputBindings>,
+) {
+    let pause_prompt = input_bindings.prompt(InputAction::Pause, active_input_device.device);
+    build_pause_menu(&mut commands, &asset_server, &pause_prompt);
+}
+
+pub fn spawn_game_over_summary(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    player_info: Res<PlayerInfo>,
+    combo: Res<Combo>,
+    high_scores: Res<HighScores>,
+    settings: Res<GameSettings>,
+) {
+    build_game_over_summary(
+        &mut commands,
+        &asset_server,
+        &player_info,
+        &combo,
+        &high_scores,
+        &settings,
+    );
+}
+
+pub fn despawn_pause_menu(
+    mut commands: Commands,
+    pause_menu_query: Query<Entity, With<PauseMenu>>,
+) {
+    if let Ok(pause_menu_entity) = pause_menu_query.get_single() {
+        commands.entity(pause_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn spawn_settings_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+    input_bindings: Res<InputBindings>,
+    rebind_state: Res<RebindState>,
+) {
+    build_settings_menu(
+        &mut commands,
+        &asset_server,
+        &settings,
+        &input_bindings,
+        &rebind_state,
+    );
+}
+
+pub fn despawn_settings_menu(
+    mut commands: Commands,
+    settings_menu_query: Query<Entity, With<SettingsMenu>>,
+) {
+    if let Ok(settings_menu_entity) = settings_menu_query.get_single() {
+        commands.entity(settings_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn spawn_achievements_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    unlocked_achievements: Res<UnlockedAchievements>,
+    high_scores: Res<HighScores>,
+    settings: Res<GameSettings>,
+) {
+    build_achievements_menu(
+        &mut commands,
+        &asset_server,
+        &unlocked_achievements,
+        &high_scores,
+        &settings,
+    );
+}
+
+pub fn despawn_achievements_menu(
+    mut commands: Commands,
+    achievements_menu_query: Query<Entity, With<AchievementsMenu>>,
+) {
+    if let Ok(achievements_menu_entity) = achievements_menu_query.get_single() {
+        commands
+            .entity(achievements_menu_entity)
+            .despawn_recursive();
+    }
+}
+
+pub fn spawn_achievement_toasts(
+    mut commands: Commands,
+    mut achievement_unlocked_event_reader: EventReader<AchievementUnlockedEvent>,
+    toast_list_query: Query<Entity, With<AchievementToastList>>,
+    asset_server: Res<AssetServer>,
+) {
+    let Ok(toast_list_entity) = toast_list_query.get_single() else {
+        return;
+    };
+
+    for event in achievement_unlocked_event_reader.iter() {
+        commands.entity(toast_list_entity).with_children(|parent| {
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: ACHIEVEMENT_TOAST_STYLE,
+                        background_color: ACHIEVEMENT_TOAST_COLOR.into(),
+                        ..default()
+                    },
+                    AchievementToast {
+                        timer: Timer::from_seconds(ACHIEVEMENT_TOAST_DURATION, TimerMode::Once),
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle::from_section(
+                        format!("Achievement unlocked: {}", event.achievement.name),
+                        get_small_button_text_style(&asset_server),
+                    ));
+                    parent.spawn(TextBundle::from_section(
+                        event.achievement.description,
+                        TextStyle {
+                            font_size: 18.0,
+                            ..get_small_button_text_style(&asset_server)
+                        },
+                    ));
+                });
+        });
+    }
+}
+
+pub fn despawn_expired_achievement_toasts(
+    mut commands: Commands,
+    mut toasts_query: Query<(Entity, &mut AchievementToast)>,
+    time: Res<Time>,
+) {
+    for (toast_entity, mut toast) in toasts_query.iter_mut() {
+        if toast.timer.tick(time.delta()).just_finished() {
+            commands.entity(toast_entity).despawn_recursive();
+        }
+    }
+}
+
+pub fn spawn_game_ui(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
+    build_game_ui(&mut commands, &asset_server);
+    // Endless mode has no bottom, so the depth is shown as a number
+    if settings.game_mode == GameMode::Endless {
+        build_depth_counter(&mut commands, &asset_server);
+    } else {
+        build_depth_ui(&mut commands, &asset_server);
+    }
+    build_boss_health_bar(&mut commands, &asset_server);
+    build_achievement_toast_list(&mut commands);
+    build_pickup_effect_timers(&mut commands, &asset_server);
+    build_touch_controls(&mut commands, &asset_server);
+}
+
+// On-screen buttons are shown only while the player uses the touch screen
+pub fn update_touch_controls_visibility(
+    mut touch_controls_query: Query<&mut Style, With<TouchControlsUI>>,
+    active_input_device: Res<ActiveInputDevice>,
+) {
+    if !active_input_device.is_changed() {
+        return;
+    }
+
+    for mut touch_controls_style in touch_controls_query.iter_mut() {
+        touch_controls_style.display = if active_input_device.device == InputDevice::Touch {
+            Display::Flex
+        } else {
+            Display::None
+        };
+    }
+}
+
+type GameUIStyleQuery<'w, 's> = Query<
+    'w,
+    's,
+    &'static mut Style,
+    (With<GameUI>, Without<HudLeftPanel>, Without<HudRightPanel>),
+>;
+
+// Switches the HUD between the regular and portrait (phone) layouts
+pub fn adapt_hud_to_aspect_ratio(
+    mut game_ui_query: GameUIStyleQuery,
+    mut left_panel_query: Query<&mut Style, (With<HudLeftPanel>, Without<HudRightPanel>)>,
+    mut right_panel_query: Query<&mut Style, (With<HudRightPanel>, Without<HudLeftPanel>)>,
+    window_query: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Ok(primary_window) = window_query.get_single() else {
+        return;
+    };
+    let is_portrait = primary_window.height() / primary_window.width() >= PORTRAIT_ASPECT_RATIO;
+
+    let (game_ui_style, left_panel_style, right_panel_style) = if is_portrait {
+        (
+            PORTRAIT_GAME_HUD_STYLE,
+            PORTRAIT_LHS_STYLE,
+            PORTRAIT_RHS_STYLE,
+        )
+    } else {
+        (GAME_HUD_STYLE, LHS_STYLE, RHS_STYLE)
+    };
+
+    // Only write the changed styles, so the layout isn't recomputed every frame
+    for mut style in game_ui_query.iter_mut() {
+        style.set_if_neq(game_ui_style.clone());
+    }
+    for mut style in left_panel_query.iter_mut() {
+        style.set_if_neq(left_panel_style.clone());
+    }
+    for mut style in right_panel_query.iter_mut() {
+        style.set_if_neq(right_panel_style.clone());
+    }
+}
+
+pub fn spawn_main_menu(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    settings: Res<GameSettings>,
+) {
+    build_main_menu(&mut commands, &asset_server, &settings);
+}
+
+pub fn despawn_main_menu(mut commands: Commands, main_menu_query: Query<Entity, With<MainMenu>>) {
+    if let Ok(main_menu_entity) = main_menu_query.get_single() {
+        commands.entity(main_menu_entity).despawn_recursive();
+    }
+}
+
+pub fn build_depth_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    // ===== Depth bar background =====
+    commands
+        .spawn(ImageBundle {
+            style: Style {
+                size: Size::new(Val::Px(25.0 * 2.), Val::Px(128. * 2.)),
+
+                position_type: PositionType::Absolute,
+                position: UiRect {
+                    left: Val::Auto,
+                    right: Val::Px(20.),
+                    top: Val::Px(250.),
+                    bottom: Val::Px(250.),
+                },
+                ..default()
+            },
+            image: asset_server
+                .load("sprites/progress_bar_background.png")
+                .into(),
+            ..default()
+        })
+        .with_children(|parent| {
+            // ===== Depth bar icon =====
+            parent.spawn((
+                ImageBundle {
+                    style: Style {
+                        size: Size::new(Val::Px(22.0 * 2.), Val::Px(19.0 * 2.)),
+                        position_type: PositionType::Absolute,
+                        // margin: UiRect::new(Val::Px(0.), Val::Px(0.), Val::Px(0.), Val::Px(1000.)),
+                        position: UiRect::new(Val::Px(0.), Val::Px(0.), Val::Px(0.), Val::Px(0.)),
+                        ..default()
+                    },
+                    z_index: ZIndex::Local(1),
+                    image: asset_server.load("sprites/progress_bar_icon.png").into(),
+                    ..default()
+                },
+                DepthBarIcon {},
+            ));
+        })
+        .id()
+}
+
+pub fn build_depth_counter(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    commands
+        .spawn((
+            TextBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    position: UiRect {
+                        right: Val::Px(20.),
+                        top: Val::Px(250.),
+                        ..default()
+                    },
+                    ..default()
+                },
+                text: Text {
+                    sections: vec![TextSection::new(
+                        "Depth 0",
+                        get_button_text_style(asset_server),
+                    )],
+                    alignment: TextAlignment::Right,
+                    ..default()
+                },
+                ..default()
+            },
+            DepthText {},
+        ))
+        .id()
+}
+
+pub fn build_boss_health_bar(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: BOSS_BAR_STYLE,
+                ..default()
+            },
+            BossHealthBar {},
+        ))
+        .with_children(|parent| {
+            // === Boss name ===
+            parent.spawn(TextBundle {
+                text: Text {
+                    sections: vec![TextSection::new(
+                        BOSS_NAME,
+                        get_small_button_text_style(asset_server),
+                    )],
+                    alignment: TextAlignment::Center,
+                    ..default()
+                },
+                ..default()
+            });
+
+            // === Health bar ===
+            parent
+                .spawn(NodeBundle {
+                    style: BOSS_BAR_BACKGROUND_STYLE,
+                    background_color: BOSS_BAR_BACKGROUND_COLOR.into(),
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        NodeBundle {
+                            style: Style {
+                                size: Size::new(Val::Percent(100.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            background_color: BOSS_BAR_FILL_COLOR.into(),
+                            ..default()
+                        },
+                        BossHealthBarFill {},
+                    ));
+
+                    // Phase markers, where the next phases start
+                    for boss_phase in BOSS_PHASES.iter().skip(1) {
+                        parent.spawn(NodeBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                position: UiRect {
+                                    left: Val::Percent(boss_phase.health_threshold * 100.0),
+                                    top: Val::Px(0.0),
+                                    ..default()
+                                },
+                                size: Size::new(Val::Px(3.0), Val::Percent(100.0)),
+                                ..default()
+                            },
+                            background_color: BOSS_BAR_PHASE_MARKER_COLOR.into(),
+                            z_index: ZIndex::Local(1),
+                            ..default()
+                        });
+                    }
+                });
+        })
+        .id()
+}
+
+pub fn build_game_ui(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: GAME_HUD_STYLE,
+                ..default()
+            },
+            GameUI {},
+        ))
+        .with_children(|parent| {
+            // LHS UI elements
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: LHS_STYLE,
+                        ..default()
+                    },
+                    HudLeftPanel {},
+                ))
+                .with_children(|parent| {
+                    // === Health bar === (LHS, top)
+                    parent
+                        .spawn(
+                            // Health bar image
+                            ImageBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(129.0), Val::Px(53.0)),
+                                    margin: UiRect::new(
+                                        Val::Px(8.0),
+                                        Val::Px(8.0),
+                                        Val::Px(8.0),
+                                        Val::Px(8.0),
+                                    ),
+                                    ..default()
+                                },
+                                image: asset_server.load("sprites/health_bg.png").into(),
+                                ..default()
+                            },
+                        )
+                        .with_children(|parent| {
+                            parent.spawn(
+                                // Health bar text
+                                (
+                                    TextBundle {
+                                        style: Style {
+                                            margin: UiRect::new(
+                                                Val::Px(8.0),
+                                                Val::Px(0.0),
+                                                Val::Px(4.0),
+                                                Val::Px(0.0),
+                                            ),
+                                            ..default()
+                                        },
+                                        text: Text {
+                                            sections: vec![TextSection::new(
+                                                PLAYER_MAX_HEALTH.to_string(),
+                                                TextStyle {
+                                                    font: asset_server
+                                                        .load("fonts/origami_mommy_regular.ttf"),
+                                                    font_size: 48.0,
+                                                    color: Color::WHITE,
+                                                },
+                                            )],
+                                            alignment: TextAlignment::Center,
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    HealthText {},
+                                ),
+                            );
+
+                            parent.spawn(
+                                // Health image
+                                ImageBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Px(30.0), Val::Px(30.0)),
+                                        margin: UiRect::new(
+                                            Val::Px(8.0),
+                                            Val::Px(8.0),
+                                            Val::Px(8.0),
+                                            Val::Px(8.0),
+                                        ),
+                                        ..default()
+                                    },
+                                    image: asset_server.load("sprites/health.png").into(),
+                                    ..default()
+                                },
+                            );
+                        });
+
+                    // === Fuel bar === (LHS, bottom)
+                    parent
+                        .spawn(
+                            // Fuel bar image
+                            ImageBundle {
+                                style: Style {
+                                    size: Size::new(Val::Px(129.0), Val::Px(53.0)),
+                                    margin: UiRect::new(
+                                        Val::Px(8.0),
+                                        Val::Px(8.0),
+                                        Val::Px(8.0),
+                                        Val::Px(8.0),
+                                    ),
+                                    ..default()
+                                },
                                 image: asset_server.load("sprites/fuel_bg.png").into(),
                                 ..default()
                             },
-                        )
-                        .with_children(|parent| {
-                            parent.spawn(
-                                // Fuel bar text
-                                (
-                                    TextBundle {
-                                        style: Style {
-                                            margin: UiRect::new(
-                                                Val::Px(8.0),
-                                                Val::Px(0.0),
-                                                Val::Px(4.0),
-                                                Val::Px(0.0),
-                                            ),
-                                            ..default()
-                                        },
-                                        text: Text {
-                                            sections: vec![TextSection::new(
-                                                PLAYER_FUEL_CAPACITY.to_string(),
-                                                TextStyle {
-                                                    font: asset_server
-                                                        .load("fonts/origami_mommy_regular.ttf"),
-                                                    font_size: 48.0,
-                                                    color: Color::WHITE,
-                                                },
-                                            )],
-                                            alignment: TextAlignment::Center,
-                                            ..default()
-                                        },
-                                        ..default()
-                                    },
-                                    FuelText {},
-                                ),
-                            );
+                        )
+                        .with_children(|parent| {
+                            parent.spawn(
+                                // Fuel bar text
+                                (
+                                    TextBundle {
+                                        style: Style {
+                                            margin: UiRect::new(
+                                                Val::Px(8.0),
+                                                Val::Px(0.0),
+                                                Val::Px(4.0),
+                                                Val::Px(0.0),
+                                            ),
+                                            ..default()
+                                        },
+                                        text: Text {
+                                            sections: vec![TextSection::new(
+                                                PLAYER_FUEL_CAPACITY.to_string(),
+                                                TextStyle {
+                                                    font: asset_server
+                                                        .load("fonts/origami_mommy_regular.ttf"),
+                                                    font_size: 48.0,
+                                                    color: Color::WHITE,
+                                                },
+                                            )],
+                                            alignment: TextAlignment::Center,
+                                            ..default()
+                                        },
+                                        ..default()
+                                    },
+                                    FuelText {},
+                                ),
+                            );
+
+                            parent.spawn(
+                                // Fuel image
+                                ImageBundle {
+                                    style: Style {
+                                        size: Size::new(Val::Px(30.0), Val::Px(30.0)),
+                                        margin: UiRect::new(
+                                            Val::Px(8.0),
+                                            Val::Px(8.0),
+                                            Val::Px(8.0),
+                                            Val::Px(8.0),
+                                        ),
+                                        ..default()
+                                    },
+                                    image: asset_server.load("sprites/fuel.png").into(),
+                                    ..default()
+                                },
+                            );
+                        });
+                });
+
+            // === Combo === (center)
+            parent
+                .spawn(NodeBundle {
+                    style: CENTER_STYLE,
+                    ..default()
+                })
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    "",
+                                    TextStyle {
+                                        font: asset_server.load("fonts/origami_mommy_regular.ttf"),
+                                        font_size: 32.0,
+                                        color: Color::WHITE,
+                                    },
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        ComboText {},
+                    ));
+                });
+
+            // RHS UI elements
+            parent
+                .spawn((
+                    NodeBundle {
+                        style: RHS_STYLE,
+                        ..default()
+                    },
+                    HudRightPanel {},
+                ))
+                .with_children(|parent| {
+                    // === Chainsaw prompt === (RHS, top)
+                    parent.spawn((
+                        ImageBundle {
+                            style: Style {
+                                size: Size::new(Val::Px(32.0 * 1.5), Val::Px(32.0 * 1.5)),
+                                ..default()
+                            },
+                            image: asset_server.load("sprites/LMB.png").into(),
+                            ..default()
+                        },
+                        ChainsawPromptImage {},
+                    ));
+
+                    parent.spawn((
+                        TextBundle {
+                            style: Style {
+                                display: Display::None,
+                                ..default()
+                            },
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    "",
+                                    TextStyle {
+                                        font: asset_server.load("fonts/origami_mommy_regular.ttf"),
+                                        font_size: 32.0,
+                                        color: Color::WHITE,
+                                    },
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        ChainsawPromptText {},
+                    ));
+                });
+            // === Blood score === (RHS, bottom)
+        })
+        .id()
+}
+
+pub fn build_main_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    settings: &GameSettings,
+) -> Entity {
+    let main_menu_entity = commands
+        .spawn((
+            NodeBundle {
+                style: MAIN_MENU_STYLE,
+                ..default()
+            },
+            MainMenu {},
+        ))
+        .with_children(|parent| {
+            // === Play Button ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    PlayButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Play",
+                                get_button_text_style(&asset_server),
+                            )],
+                            alignment: TextAlignment::Center,
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+
+            // === Difficulty Button ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: WIDE_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    DifficultyButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    format!("Difficulty: {}", settings.difficulty.name()),
+                                    get_button_text_style(asset_server),
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        DifficultyText {},
+                    ));
+                });
+
+            // === Game Mode Button ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: WIDE_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    GameModeButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    format!("Mode: {}", settings.game_mode.name()),
+                                    get_button_text_style(asset_server),
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        GameModeText {},
+                    ));
+                });
+
+            // === Settings Button ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    SettingsButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Settings",
+                                get_button_text_style(asset_server),
+                            )],
+                            alignment: TextAlignment::Center,
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+
+            // === Achievements Button ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: WIDE_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    AchievementsButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Achievements",
+                                get_button_text_style(asset_server),
+                            )],
+                            alignment: TextAlignment::Center,
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+        })
+        .id();
+
+    main_menu_entity
+}
+
+pub fn build_achievements_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    unlocked_achievements: &UnlockedAchievements,
+    high_scores: &HighScores,
+    settings: &GameSettings,
+) -> Entity {
+    let (game_mode, difficulty) = (settings.game_mode, settings.difficulty);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: MAIN_MENU_STYLE,
+                ..default()
+            },
+            AchievementsMenu {},
+        ))
+        .with_children(|parent| {
+            // === Game mode, difficulty and their best score ===
+            parent.spawn(TextBundle::from_section(
+                format!(
+                    "{} {} - best score: {}",
+                    game_mode.name(),
+                    difficulty.name(),
+                    high_scores.best(game_mode, difficulty)
+                ),
+                get_button_text_style(asset_server),
+            ));
+
+            // === Achievement list ===
+            for achievement in ACHIEVEMENTS.iter() {
+                let text_color =
+                    if unlocked_achievements.is_unlocked(game_mode, difficulty, achievement.id) {
+                        Color::WHITE
+                    } else {
+                        LOCKED_ACHIEVEMENT_TEXT_COLOR
+                    };
+
+                parent
+                    .spawn(NodeBundle {
+                        style: ACHIEVEMENT_ENTRY_STYLE,
+                        ..default()
+                    })
+                    .with_children(|parent| {
+                        parent.spawn(TextBundle::from_section(
+                            achievement.name,
+                            TextStyle {
+                                color: text_color,
+                                ..get_button_text_style(asset_server)
+                            },
+                        ));
+                        parent.spawn(TextBundle::from_section(
+                            achievement.description,
+                            TextStyle {
+                                color: text_color,
+                                ..get_small_button_text_style(asset_server)
+                            },
+                        ));
+                    });
+            }
 
-                            parent.spawn(
-                                // Fuel image
-                                ImageBundle {
-                                    style: Style {
-                                        size: Size::new(Val::Px(30.0), Val::Px(30.0)),
-                                        margin: UiRect::new(
-                                            Val::Px(8.0),
-                                            Val::Px(8.0),
-                                            Val::Px(8.0),
-                                            Val::Px(8.0),
-                                        ),
-                                        ..default()
-                                    },
-                                    image: asset_server.load("sprites/fuel.png").into(),
-                                    ..default()
-                                },
-                            );
-                        });
+            // === Back Button ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    BackButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Back",
+                                get_button_text_style(asset_server),
+                            )],
+                            alignment: TextAlignment::Center,
+                            ..default()
+                        },
+                        ..default()
+                    });
                 });
+        })
+        .id()
+}
 
-            // RHS UI elements
-            // === Blood score === (RHS, bottom)
+pub fn build_achievement_toast_list(commands: &mut Commands) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: ACHIEVEMENT_TOAST_LIST_STYLE,
+                z_index: ZIndex::Global(5),
+                ..default()
+            },
+            AchievementToastList {},
+        ))
+        .id()
+}
+
+pub fn build_pickup_effect_timers(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+) -> Entity {
+    commands
+        .spawn(NodeBundle {
+            style: PICKUP_EFFECT_LIST_STYLE,
+            ..default()
+        })
+        .with_children(|parent| {
+            // Instant pickups have nothing to count down
+            for pickup in PICKUPS
+                .iter()
+                .filter(|pickup| pickup.duration != PickupDuration::Instant)
+            {
+                parent
+                    .spawn((
+                        NodeBundle {
+                            style: PICKUP_EFFECT_TIMER_STYLE,
+                            ..default()
+                        },
+                        PickupEffectTimer { kind: pickup.kind },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn(ImageBundle {
+                            style: Style {
+                                size: PICKUP_EFFECT_ICON_SIZE,
+                                ..default()
+                            },
+                            image: asset_server.load(pickup.sprite).into(),
+                            ..default()
+                        });
+                        parent.spawn((
+                            TextBundle::from_section(
+                                pickup.name,
+                                get_small_button_text_style(asset_server),
+                            ),
+                            PickupEffectTimerText { kind: pickup.kind },
+                        ));
+                    });
+            }
         })
         .id()
 }
 
-pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
-    let main_menu_entity = commands
+pub fn build_settings_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    settings: &GameSettings,
+    input_bindings: &InputBindings,
+    rebind_state: &RebindState,
+) -> Entity {
+    commands
         .spawn((
             NodeBundle {
                 style: MAIN_MENU_STYLE,
                 ..default()
             },
-            MainMenu {},
+            SettingsMenu {},
         ))
         .with_children(|parent| {
-            // === Play Button ===
+            // === Control Scheme Button ===
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: WIDE_BUTTON_STYLE,
+                        background_color: NORMAL_BUTTON_COLOR.into(),
+                        ..default()
+                    },
+                    ControlSchemeButton {},
+                ))
+                .with_children(|parent| {
+                    parent.spawn((
+                        TextBundle {
+                            text: Text {
+                                sections: vec![TextSection::new(
+                                    format!("Controls: {}", settings.control_scheme.name()),
+                                    get_button_text_style(asset_server),
+                                )],
+                                alignment: TextAlignment::Center,
+                                ..default()
+                            },
+                            ..default()
+                        },
+                        ControlSchemeText {},
+                    ));
+                });
+
+            // === Rebind Buttons ===
+            for action in InputAction::ALL {
+                parent
+                    .spawn((
+                        ButtonBundle {
+                            style: BINDING_BUTTON_STYLE,
+                            background_color: NORMAL_BUTTON_COLOR.into(),
+                            ..default()
+                        },
+                        RebindButton { action },
+                    ))
+                    .with_children(|parent| {
+                        parent.spawn((
+                            TextBundle {
+                                text: Text {
+                                    sections: vec![TextSection::new(
+                                        get_rebind_text(action, input_bindings, rebind_state),
+                                        get_small_button_text_style(asset_server),
+                                    )],
+                                    alignment: TextAlignment::Center,
+                                    ..default()
+                                },
+                                ..default()
+                            },
+                            RebindText { action },
+                        ));
+                    });
+            }
+
+            // === Back Button ===
             parent
                 .spawn((
                     ButtonBundle {
@@ -302,14 +1525,14 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                         background_color: NORMAL_BUTTON_COLOR.into(),
                         ..default()
                     },
-                    PlayButton {},
+                    BackButton {},
                 ))
                 .with_children(|parent| {
                     parent.spawn(TextBundle {
                         text: Text {
                             sections: vec![TextSection::new(
-                                "Play",
-                                get_button_text_style(&asset_server),
+                                "Back",
+                                get_button_text_style(asset_server),
                             )],
                             alignment: TextAlignment::Center,
                             ..default()
@@ -318,7 +1541,165 @@ pub fn build_main_menu(commands: &mut Commands, asset_server: &Res<AssetServer>)
                     });
                 });
         })
-        .id();
+        .id()
+}
 
-    main_menu_entity
+pub fn build_pause_menu(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    pause_prompt: &str,
+) -> Entity {
+    let resume_prompt = format!("Press {} to resume", pause_prompt);
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    ..MAIN_MENU_STYLE
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            PauseMenu {},
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    sections: vec![TextSection::new(
+                        "Paused",
+                        TextStyle {
+                            font: asset_server.load("fonts/origami_mommy_regular.ttf"),
+                            font_size: 64.0,
+                            color: Color::WHITE,
+                        },
+                    )],
+                    alignment: TextAlignment::Center,
+                    ..default()
+                },
+                ..default()
+            });
+
+            parent.spawn(TextBundle {
+                text: Text {
+                    sections: vec![TextSection::new(
+                        resume_prompt,
+                        get_button_text_style(asset_server),
+                    )],
+                    alignment: TextAlignment::Center,
+                    ..default()
+                },
+                ..default()
+            });
+        })
+        .id()
+}
+
+pub fn build_game_over_summary(
+    commands: &mut Commands,
+    asset_server: &Res<AssetServer>,
+    player_info: &PlayerInfo,
+    combo: &Combo,
+    high_scores: &HighScores,
+    settings: &GameSettings,
+) -> Entity {
+    // High score may be recorded after this, so the current score counts as well
+    let best_score = high_scores
+        .best(settings.game_mode, settings.difficulty)
+        .max(player_info.score);
+    let summary_lines = [
+        format!("Mode: {}", settings.game_mode.name()),
+        format!("Difficulty: {}", settings.difficulty.name()),
+        format!("Score: {}", player_info.score),
+        format!("Best score: {}", best_score),
+        format!("Highest combo: {}", combo.highest),
+    ];
+
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    position_type: PositionType::Absolute,
+                    ..MAIN_MENU_STYLE
+                },
+                background_color: Color::rgba(0.0, 0.0, 0.0, 0.5).into(),
+                z_index: ZIndex::Global(10),
+                ..default()
+            },
+            GameOverSummary {},
+        ))
+        .with_children(|parent| {
+            parent.spawn(TextBundle {
+                text: Text {
+                    sections: vec![TextSection::new(
+                        "Game over",
+                        TextStyle {
+                            font: asset_server.load("fonts/origami_mommy_regular.ttf"),
+                            font_size: 64.0,
+                            color: Color::WHITE,
+                        },
+                    )],
+                    alignment: TextAlignment::Center,
+                    ..default()
+                },
+                ..default()
+            });
+
+            for summary_line in summary_lines {
+                parent.spawn(TextBundle {
+                    text: Text {
+                        sections: vec![TextSection::new(
+                            summary_line,
+                            get_button_text_style(asset_server),
+                        )],
+                        alignment: TextAlignment::Center,
+                        ..default()
+                    },
+                    ..default()
+                });
+            }
+        })
+        .id()
+}
+
+pub fn build_touch_controls(commands: &mut Commands, asset_server: &Res<AssetServer>) -> Entity {
+    commands
+        .spawn((
+            NodeBundle {
+                style: Style {
+                    display: Display::None,
+                    ..TOUCH_CONTROLS_STYLE
+                },
+                ..default()
+            },
+            TouchControlsUI {},
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    ButtonBundle {
+                        style: TOUCH_BUTTON_STYLE,
+                        background_color: Color::rgba(0.15, 0.15, 0.15, 0.6).into(),
+                        ..default()
+                    },
+                    TouchActionButton {
+                        action: InputAction::ActivateChainsaw,
+                    },
+                ))
+                .with_children(|parent| {
+                    parent.spawn(TextBundle {
+                        text: Text {
+                            sections: vec![TextSection::new(
+                                "Saw",
+                                get_button_text_style(asset_server),
+                            )],
+                            alignment: TextAlignment::Center,
+                            ..default()
+                        },
+                        ..default()
+                    });
+                });
+        })
+        .id()
 }